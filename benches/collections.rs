@@ -0,0 +1,268 @@
+mod common;
+
+use std::collections::{HashMap, VecDeque};
+
+use common::{Bench, Rng};
+use dsa_in_rust::{
+    hash_table::{
+        arrar_hash_table::ArrayHashMap, chain_hash_table::ChainHashMap,
+        open_addr_hash_table::OpenAddrHashMap,
+    },
+    queue::{
+        array_deque::ArrayDeque, array_queue::ArrayQueue, link_deque::LinkDeque,
+        link_queue::LinkQueue,
+    },
+    stack::{array_stack::ArrayStack, link_stack::LinkStack},
+};
+
+// LinkQueue and LinkDeque nodes print themselves when dropped, which
+// dominates their numbers.
+
+const SIZES: [usize; 2] = [1000, 100_000];
+
+// queue length kept while pushing and popping in turn
+const STEADY_LEN: usize = 64;
+
+fn queues(bench: &mut Bench, n: usize) {
+    bench.run(
+        "queue",
+        "ArrayQueue",
+        "push then pop",
+        n,
+        || ArrayQueue::new(n),
+        |mut queue| {
+            for i in 0..n {
+                queue.push(i).unwrap();
+            }
+            while queue.pop().is_some() {}
+        },
+    );
+    bench.run(
+        "queue",
+        "LinkQueue",
+        "push then pop",
+        n,
+        LinkQueue::new,
+        |mut queue| {
+            for i in 0..n {
+                queue.push(i);
+            }
+            while queue.pop().is_some() {}
+        },
+    );
+    bench.run(
+        "queue",
+        "VecDeque",
+        "push then pop",
+        n,
+        VecDeque::new,
+        |mut queue| {
+            for i in 0..n {
+                queue.push_back(i);
+            }
+            while queue.pop_front().is_some() {}
+        },
+    );
+
+    bench.run(
+        "queue",
+        "ArrayQueue",
+        "steady",
+        n,
+        || {
+            let mut queue = ArrayQueue::new(STEADY_LEN + 1);
+            (0..STEADY_LEN).for_each(|i| queue.push(i).unwrap());
+            queue
+        },
+        |mut queue| {
+            for i in 0..n {
+                queue.push(i).unwrap();
+                queue.pop();
+            }
+            queue
+        },
+    );
+    bench.run(
+        "queue",
+        "LinkQueue",
+        "steady",
+        n,
+        || {
+            let mut queue = LinkQueue::new();
+            (0..STEADY_LEN).for_each(|i| queue.push(i));
+            queue
+        },
+        |mut queue| {
+            for i in 0..n {
+                queue.push(i);
+                queue.pop();
+            }
+            queue
+        },
+    );
+}
+
+fn deques(bench: &mut Bench, n: usize) {
+    bench.run(
+        "deque",
+        "ArrayDeque",
+        "push both ends",
+        n,
+        || ArrayDeque::new(n),
+        |mut deque| {
+            for i in 0..n {
+                if i % 2 == 0 {
+                    deque.push_front(i).unwrap();
+                } else {
+                    deque.push_rear(i).unwrap();
+                }
+            }
+            while deque.pop_front().is_some() && deque.pop_rear().is_some() {}
+        },
+    );
+    bench.run(
+        "deque",
+        "LinkDeque",
+        "push both ends",
+        n,
+        LinkDeque::new,
+        |mut deque| {
+            for i in 0..n {
+                if i % 2 == 0 {
+                    deque.push_front(i);
+                } else {
+                    deque.push_rear(i);
+                }
+            }
+            while deque.pop_front().is_some() && deque.pop_rear().is_some() {}
+        },
+    );
+}
+
+fn stacks(bench: &mut Bench, n: usize) {
+    bench.run(
+        "stack",
+        "ArrayStack",
+        "push then pop",
+        n,
+        || ArrayStack::new(n),
+        |mut stack| {
+            for i in 0..n {
+                stack.push(i).unwrap();
+            }
+            while stack.pop().is_some() {}
+        },
+    );
+    bench.run(
+        "stack",
+        "LinkStack",
+        "push then pop",
+        n,
+        LinkStack::new,
+        |mut stack| {
+            for i in 0..n {
+                stack.push(i);
+            }
+            while stack.pop().is_some() {}
+        },
+    );
+    bench.run("stack", "Vec", "push then pop", n, Vec::new, |mut stack| {
+        for i in 0..n {
+            stack.push(i);
+        }
+        while stack.pop().is_some() {}
+    });
+}
+
+// the common interface of the maps, only used to share the bench code
+trait Map {
+    fn put(&mut self, key: usize, val: usize);
+    fn get(&mut self, key: usize) -> Option<usize>;
+    fn remove(&mut self, key: usize);
+}
+
+macro_rules! impl_map {
+    ($($map:ident),*) => {$(
+        impl Map for $map<usize> {
+            fn put(&mut self, key: usize, val: usize) {
+                $map::put(self, key, val);
+            }
+
+            fn get(&mut self, key: usize) -> Option<usize> {
+                $map::get(self, key).copied()
+            }
+
+            fn remove(&mut self, key: usize) {
+                $map::remove(self, key);
+            }
+        }
+    )*};
+}
+
+impl_map!(ChainHashMap, OpenAddrHashMap, ArrayHashMap);
+
+impl Map for HashMap<usize, usize> {
+    fn put(&mut self, key: usize, val: usize) {
+        self.insert(key, val);
+    }
+
+    fn get(&mut self, key: usize) -> Option<usize> {
+        HashMap::get(self, &key).copied()
+    }
+
+    fn remove(&mut self, key: usize) {
+        HashMap::remove(self, &key);
+    }
+}
+
+fn hash_map<M: Map>(bench: &mut Bench, name: &str, new: fn() -> M, keys: &[usize]) {
+    let n = keys.len();
+    let filled = || {
+        let mut map = new();
+        for &key in keys {
+            map.put(key, key);
+        }
+        map
+    };
+
+    bench.run("hash map", name, "put", n, new, |mut map| {
+        for &key in keys {
+            map.put(key, key);
+        }
+        map
+    });
+    bench.run("hash map", name, "get", n, filled, |mut map| {
+        let found = keys.iter().filter(|&&key| map.get(key).is_some()).count();
+        (map, found)
+    });
+    bench.run("hash map", name, "remove", n, filled, |mut map| {
+        for &key in keys {
+            map.remove(key);
+        }
+        map
+    });
+}
+
+// ArrayHashMap keeps a single entry per bucket without resizing, so its
+// numbers only show the cost of the operations, not of a working map.
+fn hash_maps(bench: &mut Bench, n: usize, rng: &mut Rng) {
+    let keys: Vec<usize> = (0..n).map(|_| rng.below(1 << 40) as usize).collect();
+
+    hash_map(bench, "ChainHashMap", ChainHashMap::new, &keys);
+    hash_map(bench, "OpenAddrHashMap", OpenAddrHashMap::new, &keys);
+    hash_map(bench, "ArrayHashMap", ArrayHashMap::new, &keys);
+    hash_map(bench, "std HashMap", HashMap::new, &keys);
+}
+
+fn main() {
+    let mut bench = Bench::from_args();
+    let mut rng = Rng::new(0x5eed);
+    for n in SIZES {
+        queues(&mut bench, n);
+        deques(&mut bench, n);
+        stacks(&mut bench, n);
+        hash_maps(&mut bench, n, &mut rng);
+    }
+
+    bench.report();
+}
@@ -0,0 +1,205 @@
+// A small std only timing harness shared by the bench targets.
+//
+// Every case is sampled until it has run for `TARGET` or `MAX_SAMPLES` times,
+// the setup of each sample is not timed. Arguments that don't start with `-`
+// filter the cases by name, e.g. `cargo bench --bench sorts -- quick`.
+
+#![allow(dead_code)]
+
+use std::{
+    env,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const TARGET: Duration = Duration::from_millis(200);
+const MIN_SAMPLES: usize = 5;
+const MAX_SAMPLES: usize = 100;
+
+// xorshift64*, seeded so every run sees the same inputs
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Distribution {
+    Random,
+    Sorted,
+    Reversed,
+    // ascending runs of length 16
+    Sawtooth,
+    // random over only a handful of keys
+    FewUnique,
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 5] = [
+        Distribution::Random,
+        Distribution::Sorted,
+        Distribution::Reversed,
+        Distribution::Sawtooth,
+        Distribution::FewUnique,
+    ];
+
+    pub fn generate(self, len: usize, rng: &mut Rng) -> Vec<i32> {
+        let len_i32 = len as i32;
+        (0..len_i32)
+            .map(|i| match self {
+                Distribution::Random => rng.next_u64() as i32,
+                Distribution::Sorted => i,
+                Distribution::Reversed => len_i32 - i,
+                Distribution::Sawtooth => i % 16,
+                Distribution::FewUnique => rng.below(4) as i32,
+            })
+            .collect()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Distribution::Random => "random",
+            Distribution::Sorted => "sorted",
+            Distribution::Reversed => "reversed",
+            Distribution::Sawtooth => "sawtooth",
+            Distribution::FewUnique => "few unique",
+        }
+    }
+}
+
+struct Row {
+    group: String,
+    name: String,
+    input: String,
+    size: usize,
+    median: Duration,
+    min: Duration,
+}
+
+pub struct Bench {
+    filters: Vec<String>,
+    rows: Vec<Row>,
+}
+
+impl Bench {
+    pub fn from_args() -> Self {
+        Bench {
+            filters: env::args()
+                .skip(1)
+                .filter(|arg| !arg.starts_with('-'))
+                .collect(),
+            rows: vec![],
+        }
+    }
+
+    // Time `routine` on fresh input from `setup`, `size` is the number of
+    // elements or operations so the report can show the time per element.
+    pub fn run<I, O>(
+        &mut self,
+        group: &str,
+        name: &str,
+        input: &str,
+        size: usize,
+        mut setup: impl FnMut() -> I,
+        mut routine: impl FnMut(I) -> O,
+    ) {
+        let full_name = format!("{}/{}/{}/{}", group, name, input, size);
+        if !self.filters.is_empty() && !self.filters.iter().any(|f| full_name.contains(f.as_str()))
+        {
+            return;
+        }
+
+        // warm up
+        black_box(routine(setup()));
+
+        let mut samples = vec![];
+        let started = Instant::now();
+        while samples.len() < MAX_SAMPLES
+            && (samples.len() < MIN_SAMPLES || started.elapsed() < TARGET)
+        {
+            let input = setup();
+            let start = Instant::now();
+            let output = routine(black_box(input));
+            samples.push(start.elapsed());
+            black_box(output);
+        }
+        samples.sort();
+
+        eprintln!("{:<60} {:>12?}", full_name, samples[samples.len() / 2]);
+        self.rows.push(Row {
+            group: group.to_string(),
+            name: name.to_string(),
+            input: input.to_string(),
+            size,
+            median: samples[samples.len() / 2],
+            min: samples[0],
+        });
+    }
+
+    pub fn report(&self) {
+        let headers = ["group", "name", "input", "size", "median", "min", "ns/elem"];
+        let cells: Vec<[String; 7]> = self
+            .rows
+            .iter()
+            .map(|row| {
+                [
+                    row.group.clone(),
+                    row.name.clone(),
+                    row.input.clone(),
+                    row.size.to_string(),
+                    format!("{:.3?}", row.median),
+                    format!("{:.3?}", row.min),
+                    format!(
+                        "{:.1}",
+                        row.median.as_nanos() as f64 / row.size.max(1) as f64
+                    ),
+                ]
+            })
+            .collect();
+
+        let mut widths = headers.map(str::len);
+        for row in &cells {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        let line = |row: &[String]| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .enumerate()
+                // text columns to the left, numbers to the right
+                .map(|(i, (cell, width))| {
+                    if i < 3 {
+                        format!("{:<width$}", cell)
+                    } else {
+                        format!("{:>width$}", cell)
+                    }
+                })
+                .collect();
+            println!("| {} |", cells.join(" | "));
+        };
+
+        println!();
+        line(&headers.map(String::from));
+        let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+        println!("|-{}-|", rule.join("-|-"));
+        for row in &cells {
+            line(row);
+        }
+    }
+}
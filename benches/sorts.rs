@@ -0,0 +1,176 @@
+mod common;
+
+use common::{Bench, Distribution, Rng};
+use dsa_in_rust::sort::{
+    bubble_sort, bucket_sort, cocktail_sort, comb_sort, counting_sort, cycle_sort, heap_sort,
+    insert_sort, intro_sort, merge_sort, network, pancake_sort, parallel,
+    quick_sort::{self, PartitionScheme, QuickSortOptions},
+    radix_sort, select_sort, shell_sort, tim_sort,
+};
+
+// quadratic sorts are only run up to this size
+const QUADRATIC_MAX: usize = 1000;
+const SIZES: [usize; 3] = [1000, 10_000, 100_000];
+
+fn three_way(nums: &mut [i32]) {
+    let options = QuickSortOptions {
+        partition: PartitionScheme::ThreeWay,
+    };
+    quick_sort::quick_sort_with(nums, i32::cmp, options);
+}
+
+fn dual_pivot(nums: &mut [i32]) {
+    let options = QuickSortOptions {
+        partition: PartitionScheme::DualPivot,
+    };
+    quick_sort::quick_sort_with(nums, i32::cmp, options);
+}
+
+// number of arrays sorted per sample in the small array group
+const SMALL_COUNT: usize = 10_000;
+
+// many tiny arrays, what sorting networks are meant for
+fn small_arrays<const N: usize>(bench: &mut Bench, rng: &mut Rng) {
+    let arrays: Vec<[i32; N]> = (0..SMALL_COUNT)
+        .map(|_| std::array::from_fn(|_| rng.next_u64() as i32))
+        .collect();
+    let input = format!("[i32; {}]", N);
+
+    bench.run(
+        "small",
+        "network",
+        &input,
+        SMALL_COUNT,
+        || arrays.clone(),
+        |mut arrays| {
+            arrays.iter_mut().for_each(network::network_sort);
+            arrays
+        },
+    );
+    if N.is_power_of_two() {
+        bench.run(
+            "small",
+            "bitonic",
+            &input,
+            SMALL_COUNT,
+            || arrays.clone(),
+            |mut arrays| {
+                arrays
+                    .iter_mut()
+                    .for_each(|arr| network::bitonic_sort_slice(arr));
+                arrays
+            },
+        );
+    }
+    bench.run(
+        "small",
+        "insert",
+        &input,
+        SMALL_COUNT,
+        || arrays.clone(),
+        |mut arrays| {
+            arrays
+                .iter_mut()
+                .for_each(|arr| insert_sort::insert_sort_slice(arr));
+            arrays
+        },
+    );
+    bench.run(
+        "small",
+        "std unstable",
+        &input,
+        SMALL_COUNT,
+        || arrays.clone(),
+        |mut arrays| {
+            arrays.iter_mut().for_each(|arr| arr.sort_unstable());
+            arrays
+        },
+    );
+}
+
+fn main() {
+    type Sort = fn(&mut [i32]);
+    let sorts: [(&str, bool, Sort); 23] = [
+        ("std stable", false, <[i32]>::sort),
+        ("std unstable", false, <[i32]>::sort_unstable),
+        ("bubble", true, bubble_sort::bubble_sort_slice),
+        ("select", true, select_sort::select_sort_slice),
+        ("insert", true, insert_sort::insert_sort_slice),
+        ("cocktail", true, cocktail_sort::cocktail_sort_slice),
+        ("pancake", true, pancake_sort::pancake_sort_slice),
+        ("cycle", true, cycle_sort::cycle_sort_slice),
+        ("comb", false, comb_sort::comb_sort_slice),
+        ("shell", false, shell_sort::shell_sort_slice),
+        ("heap", false, heap_sort::heap_sort_slice),
+        ("merge", false, merge_sort::merge_sort_slice),
+        // the two way partition is quadratic on few unique keys
+        ("quick", true, quick_sort::quick_sort_slice),
+        ("quick three way", false, three_way),
+        ("quick dual pivot", false, dual_pivot),
+        ("intro", false, intro_sort::intro_sort_slice),
+        ("tim", false, tim_sort::tim_sort_slice),
+        // random keys are too wide and go to radix sort, as in `counting_sort`
+        ("counting", false, |nums| {
+            if counting_sort::counting_sort_slice(nums).is_err() {
+                radix_sort::radix_sort_slice(nums);
+            }
+        }),
+        ("radix", false, radix_sort::radix_sort_slice),
+        ("parallel merge", false, parallel::merge_sort_slice),
+        ("parallel quick", true, parallel::quick_sort_slice),
+        ("partial", false, |nums| {
+            let n = nums.len();
+            dsa_in_rust::sort::select::partial_sort(nums, n)
+        }),
+        ("select nth", false, |nums| {
+            let n = nums.len();
+            if n > 0 {
+                dsa_in_rust::sort::select::select_nth_unstable(nums, n / 2);
+            }
+        }),
+    ];
+
+    let mut bench = Bench::from_args();
+    let mut rng = Rng::new(0x5eed);
+    for size in SIZES {
+        for distribution in Distribution::ALL {
+            let nums = distribution.generate(size, &mut rng);
+            for (name, quadratic, sort) in sorts {
+                if quadratic && size > QUADRATIC_MAX {
+                    continue;
+                }
+                bench.run(
+                    "sort",
+                    name,
+                    distribution.name(),
+                    size,
+                    || nums.clone(),
+                    |mut nums| {
+                        sort(&mut nums);
+                        nums
+                    },
+                );
+            }
+
+            let floats: Vec<f64> = nums.iter().map(|&num| num as f64).collect();
+            bench.run(
+                "sort",
+                "bucket",
+                distribution.name(),
+                size,
+                || floats.clone(),
+                |mut nums| {
+                    bucket_sort::bucket_sort_slice(&mut nums);
+                    nums
+                },
+            );
+        }
+    }
+
+    small_arrays::<4>(&mut bench, &mut rng);
+    small_arrays::<8>(&mut bench, &mut rng);
+    small_arrays::<12>(&mut bench, &mut rng);
+    small_arrays::<16>(&mut bench, &mut rng);
+
+    bench.report();
+}
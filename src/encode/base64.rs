@@ -60,7 +60,7 @@ impl Base64Encoder {
 
         for byte in &mut result {
             if *byte < 26 {
-                *byte = *byte + b'A';
+                *byte += b'A';
             } else if *byte < 52 {
                 *byte = *byte - 26 + b'a';
             } else if *byte < 62 {
@@ -82,11 +82,11 @@ impl Base64Encoder {
 
         let mut src_nums = vec![];
         for byte in src.bytes() {
-            if byte >= b'A' && byte <= b'Z' {
+            if byte.is_ascii_uppercase() {
                 src_nums.push(byte - b'A');
-            } else if byte >= b'a' && byte <= b'z' {
+            } else if byte.is_ascii_lowercase() {
                 src_nums.push(byte - b'a' + 26);
-            } else if byte >= b'0' && byte <= b'9' {
+            } else if byte.is_ascii_digit() {
                 src_nums.push(byte - b'0' + 52);
             } else if byte == b'+' {
                 src_nums.push(62);
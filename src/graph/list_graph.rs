@@ -126,7 +126,7 @@ impl<T: Debug + Eq + Hash + Clone> GraphAdjList<T> {
         res.push(start_vertex.clone());
         visited.insert(start_vertex);
 
-        if let Some(adj_list) = self.adj_list.get(&start_vertex) {
+        if let Some(adj_list) = self.adj_list.get(start_vertex) {
             for adj in adj_list {
                 if visited.contains(adj) {
                     continue;
@@ -12,6 +12,12 @@ pub struct ArrayHashMap<V: Clone + Debug> {
 
 const BUCKETS_SIZE: usize = 100;
 
+impl<V: Clone + Debug> Default for ArrayHashMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<V: Clone + Debug> ArrayHashMap<V> {
     pub fn new() -> Self {
         Self {
@@ -16,6 +16,12 @@ pub struct ChainHashMap<V: Clone + Debug> {
     buckets: Vec<Vec<Pair<V>>>,
 }
 
+impl<V: Clone + Debug> Default for ChainHashMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<V: Clone + Debug> ChainHashMap<V> {
     pub fn new() -> Self {
         Self {
@@ -74,7 +80,7 @@ impl<V: Clone + Debug> ChainHashMap<V> {
 
     fn extend(&mut self) {
         // save old pairs
-        let buckets_temp = std::mem::replace(&mut self.buckets, vec![]);
+        let buckets_temp = std::mem::take(&mut self.buckets);
 
         // create new buckets
         self.capacity *= self.extend_ratio;
@@ -152,7 +158,7 @@ mod tests {
         assert_eq!(map.get(13), None);
         assert_eq!(map.get(15), None);
 
-        for i in (0..3 * BUCKETS_SIZE).filter(|i| ![3, 13, 15].contains(&i)) {
+        for i in (0..3 * BUCKETS_SIZE).filter(|i| ![3, 13, 15].contains(i)) {
             assert_eq!(map.get(i), Some(&i));
         }
 
@@ -22,6 +22,12 @@ pub struct OpenAddrHashMap<V: Clone + Debug> {
     buckets: Vec<Option<TombstoneOrAlive<Pair<V>>>>,
 }
 
+impl<V: Clone + Debug> Default for OpenAddrHashMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<V: Clone + Debug> OpenAddrHashMap<V> {
     pub fn new() -> Self {
         Self {
@@ -59,15 +65,15 @@ impl<V: Clone + Debug> OpenAddrHashMap<V> {
                         }
                         return idx;
                     } else {
-                        idx += 1;
+                        idx = (idx + 1) % self.capacity;
                     }
                 }
                 Some(Tombstone) => {
-                    if meet_tombstone == false {
+                    if !meet_tombstone {
                         meet_tombstone = true;
                         first_tombstone = idx;
                     }
-                    idx += 1;
+                    idx = (idx + 1) % self.capacity;
                 }
                 None => {
                     if meet_tombstone {
@@ -83,9 +89,9 @@ impl<V: Clone + Debug> OpenAddrHashMap<V> {
         let idx = self.find_bucket(key);
         self.buckets[idx].as_ref().and_then(|p| {
             if let TombstoneOrAlive::Alive(p) = p {
-                return Some(&p.val);
+                Some(&p.val)
             } else {
-                return None;
+                None
             }
         })
     }
@@ -119,7 +125,7 @@ impl<V: Clone + Debug> OpenAddrHashMap<V> {
 
     fn extend(&mut self) {
         // save old pairs
-        let buckets_temp = std::mem::replace(&mut self.buckets, vec![]);
+        let buckets_temp = std::mem::take(&mut self.buckets);
 
         // create new buckets
         self.capacity *= self.extend_ratio;
@@ -198,10 +204,24 @@ mod tests {
         assert_eq!(map.get(13), None);
         assert_eq!(map.get(15), None);
 
-        for i in (0..3 * BUCKETS_SIZE).filter(|i| ![3, 13, 15].contains(&i)) {
+        for i in (0..3 * BUCKETS_SIZE).filter(|i| ![3, 13, 15].contains(i)) {
             assert_eq!(map.get(i), Some(&i));
         }
 
         map.print();
     }
+
+    #[test]
+    fn wrap_around() {
+        let mut map = OpenAddrHashMap::<usize>::new();
+
+        // both hash to the last bucket, the second probes past the end
+        map.put(9, 9);
+        map.put(19, 19);
+
+        assert_eq!(map.get(9), Some(&9));
+        assert_eq!(map.get(19), Some(&19));
+        map.remove(9);
+        assert_eq!(map.get(19), Some(&19));
+    }
 }
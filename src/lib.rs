@@ -5,3 +5,4 @@ pub mod tree;
 pub mod graph;
 pub mod sort;
 pub mod encode;
+pub mod search;
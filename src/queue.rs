@@ -2,3 +2,220 @@ pub mod array_deque;
 pub mod array_queue;
 pub mod link_deque;
 pub mod link_queue;
+
+use std::ops::Deref;
+
+// First in, first out collection, implemented by every queue backing so code
+// can be written once for all of them.
+pub trait Queue<T> {
+    // a borrowed element, `&T` for the array backings and a `Ref` guard for
+    // the linked ones whose nodes live in a RefCell
+    type Ref<'a>: Deref<Target = T>
+    where
+        Self: 'a;
+
+    // adds to the rear, gives `val` back if the queue is full
+    fn push(&mut self, val: T) -> Result<(), T>;
+
+    // removes from the front
+    fn pop(&mut self) -> Option<T>;
+
+    // the front, next to be popped
+    fn peek(&self) -> Option<Self::Ref<'_>>;
+
+    fn size(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    // elements from the front to the rear
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone;
+}
+
+// Double ended queue. As a `Queue` it pushes to the rear and pops from the
+// front.
+pub trait Deque<T>: Queue<T> {
+    // gives `val` back if the deque is full
+    fn push_front(&mut self, val: T) -> Result<(), T>;
+
+    // gives `val` back if the deque is full
+    fn push_rear(&mut self, val: T) -> Result<(), T>;
+
+    fn pop_front(&mut self) -> Option<T>;
+
+    fn pop_rear(&mut self) -> Option<T>;
+
+    fn peek_front(&self) -> Option<Self::Ref<'_>>;
+
+    fn peek_rear(&self) -> Option<Self::Ref<'_>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use array_deque::ArrayDeque;
+    use array_queue::ArrayQueue;
+    use link_deque::LinkDeque;
+    use link_queue::LinkQueue;
+
+    // Scenarios every implementation has to pass, `new` makes an empty queue
+    // with room for at least 1000 elements.
+    fn conformance<Q: Queue<i32>>(new: impl Fn() -> Q) {
+        // empty
+        let mut queue = new();
+        assert!(queue.is_empty());
+        assert_eq!(queue.size(), 0);
+        assert!(queue.peek().is_none());
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.to_vec(), vec![]);
+
+        // first in, first out
+        let mut queue = new();
+        for i in 0..10 {
+            assert_eq!(queue.push(i), Ok(()));
+            assert_eq!(queue.peek().as_deref(), Some(&0));
+            assert_eq!(queue.size(), i as usize + 1);
+        }
+        assert_eq!(queue.to_vec(), (0..10).collect::<Vec<_>>());
+        for i in 0..10 {
+            assert_eq!(queue.pop(), Some(i));
+        }
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+
+        // peek leaves the queue alone
+        let mut queue = new();
+        queue.push(7).unwrap();
+        assert_eq!(queue.peek().as_deref(), Some(&7));
+        assert_eq!(queue.peek().as_deref(), Some(&7));
+        assert_eq!(queue.size(), 1);
+
+        // usable again after being emptied
+        queue.pop();
+        queue.push(8).unwrap();
+        assert_eq!(queue.to_vec(), vec![8]);
+
+        // interleaved pushes and pops against a VecDeque as the model
+        let mut queue = new();
+        let mut model = VecDeque::new();
+        for i in 0..1000 {
+            if i % 3 == 2 {
+                assert_eq!(queue.pop(), model.pop_front());
+            } else {
+                queue.push(i).unwrap();
+                model.push_back(i);
+            }
+            assert_eq!(queue.peek().as_deref(), model.front());
+            assert_eq!(queue.size(), model.len());
+        }
+        assert_eq!(queue.to_vec(), Vec::from(model));
+    }
+
+    fn deque_conformance<D: Deque<i32>>(new: impl Fn() -> D) {
+        conformance(&new);
+
+        // both ends of an empty deque
+        let mut deque = new();
+        assert!(deque.peek_front().is_none());
+        assert!(deque.peek_rear().is_none());
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.pop_rear(), None);
+
+        // a single element is both ends
+        deque.push_front(1).unwrap();
+        assert_eq!(deque.peek_front().as_deref(), Some(&1));
+        assert_eq!(deque.peek_rear().as_deref(), Some(&1));
+        assert_eq!(deque.pop_rear(), Some(1));
+        assert!(deque.is_empty());
+
+        // every operation mixed, against a VecDeque as the model
+        let mut deque = new();
+        let mut model = VecDeque::new();
+        for i in 0..1000 {
+            match i % 7 {
+                0 | 3 => {
+                    deque.push_front(i).unwrap();
+                    model.push_front(i);
+                }
+                1 | 4 => {
+                    deque.push_rear(i).unwrap();
+                    model.push_back(i);
+                }
+                2 => assert_eq!(deque.pop_front(), model.pop_front()),
+                5 => assert_eq!(deque.pop_rear(), model.pop_back()),
+                _ => {
+                    // as a queue
+                    deque.push(i).unwrap();
+                    model.push_back(i);
+                    assert_eq!(deque.pop(), model.pop_front());
+                }
+            }
+            assert_eq!(deque.peek_front().as_deref(), model.front());
+            assert_eq!(deque.peek_rear().as_deref(), model.back());
+            assert_eq!(deque.peek().as_deref(), model.front());
+            assert_eq!(deque.size(), model.len());
+        }
+        assert_eq!(deque.to_vec(), Vec::from(model));
+    }
+
+    // only uses the trait, works for any backing
+    fn rotate<Q: Queue<i32>>(queue: &mut Q, k: usize) {
+        for _ in 0..k {
+            if let Some(front) = queue.pop() {
+                queue.push(front).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn array_queue() {
+        conformance(|| ArrayQueue::new(1000));
+
+        let mut queue = ArrayQueue::new(3);
+        for i in 1..=3 {
+            queue.push(i).unwrap();
+        }
+        rotate(&mut queue, 4);
+        assert_eq!(queue.to_vec(), [2, 3, 1]);
+
+        // full, across the wrap around too
+        assert_eq!(queue.push(4), Err(4));
+        assert_eq!(Queue::push(&mut queue, 4), Err(4));
+        assert_eq!(Queue::to_vec(&queue), [2, 3, 1]);
+    }
+
+    #[test]
+    fn link_queue() {
+        conformance(LinkQueue::new);
+
+        let mut queue: LinkQueue<i32> = LinkQueue::new();
+        for i in 1..=3 {
+            queue.push(i);
+        }
+        rotate(&mut queue, 4);
+        assert_eq!(queue.to_vec(), [2, 3, 1]);
+    }
+
+    #[test]
+    fn array_deque() {
+        deque_conformance(|| ArrayDeque::new(1000));
+
+        let mut deque = ArrayDeque::new(2);
+        Deque::push_front(&mut deque, 1).unwrap();
+        Deque::push_front(&mut deque, 0).unwrap();
+        assert_eq!(Deque::push_front(&mut deque, -1), Err(-1));
+        assert_eq!(Deque::push_rear(&mut deque, 2), Err(2));
+        assert_eq!(Queue::push(&mut deque, 2), Err(2));
+        assert_eq!(Queue::to_vec(&deque), [0, 1]);
+    }
+
+    #[test]
+    fn link_deque() {
+        deque_conformance(LinkDeque::new);
+    }
+}
@@ -1,3 +1,5 @@
+use super::{Deque, Queue};
+
 pub struct ArrayDeque<T> {
     array: Vec<T>,
     front: usize,
@@ -15,10 +17,20 @@ impl<T: Default + Clone> ArrayDeque<T> {
         }
     }
 
-    pub fn push_front(&mut self, val: T) {
+    // gives `val` back if the deque is full
+    pub fn push_front(&mut self, val: T) -> Result<(), T> {
+        self.try_push_front(val)
+    }
+
+    // gives `val` back if the deque is full
+    pub fn push_rear(&mut self, val: T) -> Result<(), T> {
+        self.try_push_rear(val)
+    }
+
+    // same as `push_front`
+    pub fn try_push_front(&mut self, val: T) -> Result<(), T> {
         if self.is_full() {
-            println!("Queue fulled.");
-            return;
+            return Err(val);
         }
 
         // first plus capacity to prevent unsign integar overflow
@@ -26,17 +38,19 @@ impl<T: Default + Clone> ArrayDeque<T> {
         self.array[front] = val;
         self.front = front;
         self.size += 1;
+        Ok(())
     }
 
-    pub fn push_rear(&mut self, val: T) {
+    // same as `push_rear`
+    pub fn try_push_rear(&mut self, val: T) -> Result<(), T> {
         if self.is_full() {
-            println!("Queue fulled.");
-            return;
+            return Err(val);
         }
 
         let rear = (self.front + self.size) % self.capacity;
         self.array[rear] = val;
         self.size += 1;
+        Ok(())
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
@@ -93,15 +107,63 @@ impl<T: Default + Clone> ArrayDeque<T> {
     }
 
     pub fn to_vec(&self) -> Vec<T> {
-        let mut v = vec![Default::default(); self.size()];
-        let mut j = self.front;
         let cap = self.capacity();
+        (self.front..self.front + self.size())
+            .map(|j| self.array[j % cap].clone())
+            .collect()
+    }
+}
 
-        for i in 0..self.size() {
-            v[i] = self.array[j % cap].clone();
-            j += 1;
-        }
-        v
+impl<T: Default + Clone> Queue<T> for ArrayDeque<T> {
+    type Ref<'a>
+        = &'a T
+    where
+        T: 'a;
+
+    fn push(&mut self, val: T) -> Result<(), T> {
+        self.try_push_rear(val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_front()
+    }
+
+    fn size(&self) -> usize {
+        ArrayDeque::size(self)
+    }
+
+    fn to_vec(&self) -> Vec<T> {
+        ArrayDeque::to_vec(self)
+    }
+}
+
+impl<T: Default + Clone> Deque<T> for ArrayDeque<T> {
+    fn push_front(&mut self, val: T) -> Result<(), T> {
+        self.try_push_front(val)
+    }
+
+    fn push_rear(&mut self, val: T) -> Result<(), T> {
+        self.try_push_rear(val)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        ArrayDeque::pop_front(self)
+    }
+
+    fn pop_rear(&mut self) -> Option<T> {
+        ArrayDeque::pop_rear(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        ArrayDeque::peek_front(self)
+    }
+
+    fn peek_rear(&self) -> Option<&T> {
+        ArrayDeque::peek_rear(self)
     }
 }
 
@@ -112,9 +174,9 @@ mod tests {
     #[test]
     fn front() {
         let mut deque = ArrayDeque::new(10);
-        deque.push_front(1);
-        deque.push_front(2);
-        deque.push_front(3);
+        deque.push_front(1).unwrap();
+        deque.push_front(2).unwrap();
+        deque.push_front(3).unwrap();
 
         assert_eq!(deque.size(), 3);
         assert!(!deque.is_empty());
@@ -137,9 +199,9 @@ mod tests {
     #[test]
     fn rear() {
         let mut deque = ArrayDeque::new(10);
-        deque.push_rear(1);
-        deque.push_rear(2);
-        deque.push_rear(3);
+        deque.push_rear(1).unwrap();
+        deque.push_rear(2).unwrap();
+        deque.push_rear(3).unwrap();
 
         assert_eq!(deque.size(), 3);
         assert!(!deque.is_empty());
@@ -162,12 +224,12 @@ mod tests {
     #[test]
     fn all() {
         let mut deque = ArrayDeque::new(10);
-        deque.push_front(3);
-        deque.push_front(2);
-        deque.push_front(1);
-        deque.push_rear(4);
-        deque.push_rear(5);
-        deque.push_rear(6);
+        deque.push_front(3).unwrap();
+        deque.push_front(2).unwrap();
+        deque.push_front(1).unwrap();
+        deque.push_rear(4).unwrap();
+        deque.push_rear(5).unwrap();
+        deque.push_rear(6).unwrap();
 
         assert_eq!(deque.size(), 6);
         assert!(!deque.is_empty());
@@ -190,10 +252,10 @@ mod tests {
         assert_eq!(deque.peek_front(), Some(&5));
         assert_eq!(deque.peek_rear(), Some(&6));
 
-        deque.push_rear(7);
-        deque.push_rear(8);
-        deque.push_front(4);
-        deque.push_front(3);
+        deque.push_rear(7).unwrap();
+        deque.push_rear(8).unwrap();
+        deque.push_front(4).unwrap();
+        deque.push_front(3).unwrap();
 
         assert_eq!(deque.size(), 6);
         assert!(!deque.is_empty());
@@ -213,4 +275,16 @@ mod tests {
         assert!(deque.is_empty());
         assert_eq!(deque.size(), 0);
     }
+
+    #[test]
+    fn try_push() {
+        let mut deque = ArrayDeque::new(2);
+        assert_eq!(deque.try_push_rear(1), Ok(()));
+        assert_eq!(deque.try_push_front(0), Ok(()));
+        assert_eq!(deque.try_push_front(-1), Err(-1));
+        assert_eq!(deque.try_push_rear(2), Err(2));
+        assert_eq!(deque.push_front(-1), Err(-1));
+        assert_eq!(deque.push_rear(2), Err(2));
+        assert_eq!(deque.to_vec(), [0, 1]);
+    }
 }
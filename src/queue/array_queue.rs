@@ -1,3 +1,5 @@
+use super::Queue;
+
 pub struct ArrayQueue<T> {
     array: Vec<T>,
     front: usize,
@@ -15,15 +17,21 @@ impl<T: Default + Clone> ArrayQueue<T> {
         }
     }
 
-    pub fn push(&mut self, val: T) {
+    // gives `val` back if the queue is full
+    pub fn push(&mut self, val: T) -> Result<(), T> {
+        self.try_push(val)
+    }
+
+    // same as `push`
+    pub fn try_push(&mut self, val: T) -> Result<(), T> {
         if self.size == self.capacity {
-            println!("Queue fulled.");
-            return;
+            return Err(val);
         }
 
         let rear = (self.front + self.size) % self.capacity;
         self.array[rear] = val;
         self.size += 1;
+        Ok(())
     }
 
     pub fn pop(&mut self) -> Option<T> {
@@ -58,15 +66,37 @@ impl<T: Default + Clone> ArrayQueue<T> {
     }
 
     pub fn to_vec(&self) -> Vec<T> {
-        let mut v = vec![Default::default(); self.size()];
-        let mut j = self.front;
         let cap = self.capacity();
+        (self.front..self.front + self.size())
+            .map(|j| self.array[j % cap].clone())
+            .collect()
+    }
+}
 
-        for i in 0..self.size() {
-            v[i] = self.array[j % cap].clone();
-            j += 1;
-        }
-        v
+impl<T: Default + Clone> Queue<T> for ArrayQueue<T> {
+    type Ref<'a>
+        = &'a T
+    where
+        T: 'a;
+
+    fn push(&mut self, val: T) -> Result<(), T> {
+        self.try_push(val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        ArrayQueue::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        ArrayQueue::peek(self)
+    }
+
+    fn size(&self) -> usize {
+        ArrayQueue::size(self)
+    }
+
+    fn to_vec(&self) -> Vec<T> {
+        ArrayQueue::to_vec(self)
     }
 }
 
@@ -77,14 +107,14 @@ mod tests {
     #[test]
     fn push_pop() {
         let mut queue = ArrayQueue::new(10);
-        queue.push(1);
-        queue.push(2);
-        queue.push(3);
+        queue.push(1).unwrap();
+        queue.push(2).unwrap();
+        queue.push(3).unwrap();
 
         assert_eq!(queue.size(), 3);
         assert!(!queue.is_empty());
 
-        assert_eq!(queue.to_vec(), [1,2,3]);
+        assert_eq!(queue.to_vec(), [1, 2, 3]);
 
         assert_eq!(queue.peek(), Some(&1));
         assert_eq!(queue.pop(), Some(1));
@@ -98,4 +128,18 @@ mod tests {
         assert_eq!(queue.size(), 0);
         assert!(queue.is_empty());
     }
+
+    #[test]
+    fn try_push() {
+        let mut queue = ArrayQueue::new(2);
+        assert_eq!(queue.try_push(1), Ok(()));
+        assert_eq!(queue.try_push(2), Ok(()));
+        assert_eq!(queue.try_push(3), Err(3));
+        assert_eq!(queue.push(3), Err(3));
+        assert_eq!(queue.to_vec(), [1, 2]);
+
+        queue.pop();
+        assert_eq!(queue.try_push(3), Ok(()));
+        assert_eq!(queue.to_vec(), [2, 3]);
+    }
 }
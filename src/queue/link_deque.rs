@@ -1,14 +1,17 @@
 use std::{
-    cell::{RefCell, RefMut},
+    cell::{Ref, RefCell, RefMut},
     fmt::Debug,
     rc::Rc,
     vec,
 };
 
+use super::{Deque, Queue};
+
 #[derive(Debug)]
 pub struct LinkDeque<T: Debug> {
     front: Option<Link<T>>,
     rear: Option<Link<T>>,
+    size: usize,
 }
 
 type Link<T> = Rc<RefCell<Node<T>>>;
@@ -30,11 +33,18 @@ impl<T: Debug> Node<T> {
     }
 }
 
+impl<T: Clone + Debug> Default for LinkDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Clone + Debug> LinkDeque<T> {
     pub fn new() -> Self {
         LinkDeque {
             front: None,
             rear: None,
+            size: 0,
         }
     }
 
@@ -47,6 +57,7 @@ impl<T: Clone + Debug> LinkDeque<T> {
             self.rear = Some(Rc::clone(&new_front));
         }
         self.front = Some(new_front);
+        self.size += 1;
     }
 
     pub fn push_rear(&mut self, val: T) {
@@ -58,6 +69,7 @@ impl<T: Clone + Debug> LinkDeque<T> {
             self.front = Some(Rc::clone(&new_rear));
         }
         self.rear = Some(new_rear);
+        self.size += 1;
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
@@ -71,6 +83,7 @@ impl<T: Clone + Debug> LinkDeque<T> {
                     self.rear.take();
                 }
             }
+            self.size -= 1;
             Rc::try_unwrap(old_front)
                 .ok()
                 .unwrap()
@@ -91,6 +104,7 @@ impl<T: Clone + Debug> LinkDeque<T> {
                     self.front.take();
                 }
             }
+            self.size -= 1;
             Rc::try_unwrap(old_rear)
                 .ok()
                 .unwrap()
@@ -116,6 +130,10 @@ impl<T: Clone + Debug> LinkDeque<T> {
         self.front.is_none() && self.rear.is_none()
     }
 
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
     pub fn to_vec(&self) -> Vec<T> {
         self._to_vec(self.front.as_ref())
     }
@@ -130,6 +148,68 @@ impl<T: Clone + Debug> LinkDeque<T> {
     }
 }
 
+// peeks through shared `Ref`s, unlike the inherent `RefMut` ones
+impl<T: Clone + Debug> Queue<T> for LinkDeque<T> {
+    type Ref<'a>
+        = Ref<'a, T>
+    where
+        T: 'a;
+
+    // never full
+    fn push(&mut self, val: T) -> Result<(), T> {
+        LinkDeque::push_rear(self, val);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        LinkDeque::pop_front(self)
+    }
+
+    fn peek(&self) -> Option<Ref<'_, T>> {
+        Deque::peek_front(self)
+    }
+
+    fn size(&self) -> usize {
+        LinkDeque::size(self)
+    }
+
+    fn to_vec(&self) -> Vec<T> {
+        LinkDeque::to_vec(self)
+    }
+}
+
+impl<T: Clone + Debug> Deque<T> for LinkDeque<T> {
+    fn push_front(&mut self, val: T) -> Result<(), T> {
+        LinkDeque::push_front(self, val);
+        Ok(())
+    }
+
+    fn push_rear(&mut self, val: T) -> Result<(), T> {
+        LinkDeque::push_rear(self, val);
+        Ok(())
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        LinkDeque::pop_front(self)
+    }
+
+    fn pop_rear(&mut self) -> Option<T> {
+        LinkDeque::pop_rear(self)
+    }
+
+    fn peek_front(&self) -> Option<Ref<'_, T>> {
+        self.front
+            .as_ref()
+            .map(|node| Ref::map(node.borrow(), |node| &node.val))
+    }
+
+    fn peek_rear(&self) -> Option<Ref<'_, T>> {
+        self.rear
+            .as_ref()
+            .map(|node| Ref::map(node.borrow(), |node| &node.val))
+    }
+}
+
 impl<T: Debug> Drop for Node<T> {
     fn drop(&mut self) {
         println!("Before drop node: {:?}", self.val);
@@ -169,6 +249,7 @@ mod tests {
         assert!(!deque.is_empty());
 
         assert_eq!(deque.to_vec(), [3, 2, 1]);
+        assert_eq!(deque.size(), 3);
 
         assert_eq!(*deque.peek_front().unwrap(), 3);
         assert_eq!(deque.pop_front(), Some(3));
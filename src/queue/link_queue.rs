@@ -5,6 +5,8 @@ use std::{
     vec,
 };
 
+use super::Queue;
+
 #[derive(Debug)]
 pub struct LinkQueue<T: Debug> {
     front: Option<Link<T>>,
@@ -26,6 +28,12 @@ impl<T: Debug> Node<T> {
     }
 }
 
+impl<T: Clone + Debug> Default for LinkQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Clone + Debug> LinkQueue<T> {
     pub fn new() -> Self {
         LinkQueue {
@@ -95,6 +103,35 @@ impl<T: Clone + Debug> LinkQueue<T> {
     }
 }
 
+impl<T: Clone + Debug> Queue<T> for LinkQueue<T> {
+    type Ref<'a>
+        = Ref<'a, T>
+    where
+        T: 'a;
+
+    // never full
+    fn push(&mut self, val: T) -> Result<(), T> {
+        LinkQueue::push(self, val);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        LinkQueue::pop(self)
+    }
+
+    fn peek(&self) -> Option<Ref<'_, T>> {
+        LinkQueue::peek(self)
+    }
+
+    fn size(&self) -> usize {
+        LinkQueue::size(self)
+    }
+
+    fn to_vec(&self) -> Vec<T> {
+        LinkQueue::to_vec(self)
+    }
+}
+
 impl<T: Debug> Drop for Node<T> {
     fn drop(&mut self) {
         println!("Before drop node: {:?}", self.val);
@@ -0,0 +1,5 @@
+pub mod binary_search;
+pub mod exponential_search;
+pub mod interpolation_search;
+pub mod is_sorted;
+pub mod merge;
@@ -0,0 +1,137 @@
+use std::{cmp::Ordering, ops::Range};
+
+// Index of the first element for which `pred` is false, `nums` must have
+// every element matching `pred` before every element that doesn't.
+pub fn partition_point<T, P>(nums: &[T], mut pred: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    // [0, lo) match, [hi, n) don't
+    let (mut lo, mut hi) = (0, nums.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(&nums[mid]) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+// first index whose element is not less than `target`
+pub fn lower_bound<T: Ord>(nums: &[T], target: &T) -> usize {
+    lower_bound_by(nums, |x| x.cmp(target))
+}
+
+// `f` tells how an element compares to the target
+pub fn lower_bound_by<T, F>(nums: &[T], mut f: F) -> usize
+where
+    F: FnMut(&T) -> Ordering,
+{
+    partition_point(nums, |x| f(x) == Ordering::Less)
+}
+
+pub fn lower_bound_by_key<T, K, F>(nums: &[T], key: &K, mut f: F) -> usize
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    lower_bound_by(nums, |x| f(x).cmp(key))
+}
+
+// first index whose element is greater than `target`
+pub fn upper_bound<T: Ord>(nums: &[T], target: &T) -> usize {
+    upper_bound_by(nums, |x| x.cmp(target))
+}
+
+pub fn upper_bound_by<T, F>(nums: &[T], mut f: F) -> usize
+where
+    F: FnMut(&T) -> Ordering,
+{
+    partition_point(nums, |x| f(x) != Ordering::Greater)
+}
+
+pub fn upper_bound_by_key<T, K, F>(nums: &[T], key: &K, mut f: F) -> usize
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    upper_bound_by(nums, |x| f(x).cmp(key))
+}
+
+// indices of every element equal to `target`, empty where it would go if
+// there is none
+pub fn equal_range<T: Ord>(nums: &[T], target: &T) -> Range<usize> {
+    equal_range_by(nums, |x| x.cmp(target))
+}
+
+pub fn equal_range_by<T, F>(nums: &[T], mut f: F) -> Range<usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
+    let start = lower_bound_by(nums, &mut f);
+    // the upper bound can't be before the lower one
+    let end = start + upper_bound_by(&nums[start..], f);
+    start..end
+}
+
+pub fn equal_range_by_key<T, K, F>(nums: &[T], key: &K, mut f: F) -> Range<usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    equal_range_by(nums, |x| f(x).cmp(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9];
+
+        assert_eq!(lower_bound(&nums, &3), 3);
+        assert_eq!(upper_bound(&nums, &3), 5);
+        assert_eq!(equal_range(&nums, &3), 3..5);
+        assert_eq!(equal_range(&nums, &1), 0..1);
+        assert_eq!(equal_range(&nums, &9), 11..12);
+
+        // missing targets give an empty range where they would go
+        assert_eq!(equal_range(&nums, &0), 0..0);
+        assert_eq!(equal_range(&nums, &10), 12..12);
+        assert_eq!(equal_range(&[1, 3], &2), 1..1);
+
+        assert_eq!(equal_range(&[], &1), 0..0);
+        assert_eq!(equal_range(&[7; 100], &7), 0..100);
+    }
+
+    #[test]
+    fn by() {
+        let nums = vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1];
+        assert_eq!(lower_bound_by(&nums, |x| 4.cmp(x)), 5);
+        assert_eq!(upper_bound_by(&nums, |x| 4.cmp(x)), 7);
+
+        let words = vec!["fig", "pear", "kiwi", "apple", "banana"];
+        assert_eq!(lower_bound_by_key(&words, &4, |w| w.len()), 1);
+        assert_eq!(upper_bound_by_key(&words, &4, |w| w.len()), 3);
+        assert_eq!(equal_range_by_key(&words, &5, |w| w.len()), 3..4);
+    }
+
+    #[test]
+    fn same_as_std() {
+        let nums: Vec<i32> = (0..500).map(|i| i / 3 * 2).collect();
+        for target in -1..340 {
+            assert_eq!(
+                lower_bound(&nums, &target),
+                nums.partition_point(|&x| x < target)
+            );
+            assert_eq!(
+                upper_bound(&nums, &target),
+                nums.partition_point(|&x| x <= target)
+            );
+        }
+        assert_eq!(partition_point(&nums, |&x| x < 100), 150);
+    }
+}
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+
+use super::binary_search::partition_point;
+
+// Like `binary_search`, Ok with the index of a match or Err with where the
+// target would go, in O(log i) comparisons for a target found at index i.
+pub fn exponential_search<T: Ord>(nums: &[T], target: &T) -> Result<usize, usize> {
+    exponential_search_by(nums, |x| x.cmp(target))
+}
+
+// `f` tells how an element compares to the target
+pub fn exponential_search_by<T, F>(nums: &[T], mut f: F) -> Result<usize, usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
+    let i = exponential_partition_point(nums, |x| f(x) == Ordering::Less);
+    match nums.get(i) {
+        Some(x) if f(x) == Ordering::Equal => Ok(i),
+        _ => Err(i),
+    }
+}
+
+pub fn exponential_search_by_key<T, K, F>(nums: &[T], key: &K, mut f: F) -> Result<usize, usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    exponential_search_by(nums, |x| f(x).cmp(key))
+}
+
+// Gallop over 1, 2, 4, ... elements to bracket the partition point of
+// `pred`, then binary search inside the bracket. Cheaper than a plain
+// binary search when the point is near the front.
+pub fn exponential_partition_point<T, P>(nums: &[T], mut pred: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    let (mut lo, mut step) = (0, 1);
+    while lo + step <= nums.len() && pred(&nums[lo + step - 1]) {
+        lo += step;
+        step *= 2;
+    }
+    let hi = (lo + step).min(nums.len());
+    lo + partition_point(&nums[lo..hi], pred)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9];
+
+        assert_eq!(exponential_search(&nums, &1), Ok(0));
+        assert_eq!(exponential_search(&nums, &9), Ok(11));
+        assert_eq!(exponential_search(&nums, &3), Ok(3));
+        assert_eq!(exponential_search(&nums, &0), Err(0));
+        assert_eq!(exponential_search(&nums, &10), Err(12));
+        assert_eq!(exponential_search(&[], &1), Err(0));
+        assert_eq!(exponential_partition_point(&nums, |&x| x < 4), 5);
+    }
+
+    #[test]
+    fn by() {
+        let words = vec!["fig", "pear", "kiwi", "apple", "banana"];
+        assert_eq!(exponential_search_by_key(&words, &5, |w| w.len()), Ok(3));
+        assert_eq!(exponential_search_by_key(&words, &2, |w| w.len()), Err(0));
+
+        let nums = vec![9, 8, 7, 6, 5];
+        assert_eq!(exponential_search_by(&nums, |x| 6.cmp(x)), Ok(3));
+    }
+
+    #[test]
+    fn comparisons() {
+        let nums: Vec<i32> = (0..1 << 20).collect();
+        for target in [0, 1, 2, 5, 100, 4096, (1 << 20) - 1] {
+            let mut count = 0;
+            let found = exponential_search_by(&nums, |x| {
+                count += 1;
+                x.cmp(&target)
+            });
+            assert_eq!(found, Ok(target as usize));
+            // about two probes per doubling, wherever the array ends
+            let bits = 32 - (target as u32 + 1).leading_zeros() as usize;
+            assert!(
+                count <= 2 * bits + 3,
+                "{} comparisons for {}",
+                count,
+                target
+            );
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::cmp::Ordering;
+
+use crate::sort::radix_sort::RadixKey;
+
+// Probe where the target would be if the keys were evenly spread between
+// the two ends, O(log log n) probes on uniform keys and O(n) at worst.
+// Ok with the index of a match or Err with where the target would go.
+pub fn interpolation_search<T: RadixKey + Ord>(nums: &[T], target: &T) -> Result<usize, usize> {
+    let key = target.radix_key();
+
+    // the target is in nums[lo..hi] if anywhere
+    let (mut lo, mut hi) = (0, nums.len());
+    while lo < hi {
+        let (first, last) = (nums[lo].radix_key(), nums[hi - 1].radix_key());
+        if key < first {
+            return Err(lo);
+        }
+        if key > last {
+            return Err(hi);
+        }
+
+        let mid = if first == last {
+            lo
+        } else {
+            let offset = (key - first) as u128 * (hi - 1 - lo) as u128 / (last - first) as u128;
+            lo + offset as usize
+        };
+        match nums[mid].cmp(target) {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return Ok(mid),
+        }
+    }
+    Err(lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9];
+
+        assert_eq!(interpolation_search(&nums, &1), Ok(0));
+        assert_eq!(interpolation_search(&nums, &9), Ok(11));
+        assert_eq!(interpolation_search(&nums, &5), Ok(7));
+        let found = interpolation_search(&nums, &3).unwrap();
+        assert_eq!(nums[found], 3);
+
+        assert_eq!(interpolation_search(&nums, &0), Err(0));
+        assert_eq!(interpolation_search(&nums, &10), Err(12));
+        assert_eq!(interpolation_search(&[1, 3], &2), Err(1));
+        assert_eq!(interpolation_search::<i32>(&[], &1), Err(0));
+    }
+
+    #[test]
+    fn extremes() {
+        let nums = vec![i64::MIN, -5, 0, 7, i64::MAX];
+        for (i, num) in nums.iter().enumerate() {
+            assert_eq!(interpolation_search(&nums, num), Ok(i));
+        }
+        assert_eq!(interpolation_search(&nums, &1), Err(3));
+
+        let nums = vec![0u64, 1, 2, u64::MAX];
+        assert_eq!(interpolation_search(&nums, &2), Ok(2));
+        assert_eq!(interpolation_search(&nums, &3), Err(3));
+    }
+
+    #[test]
+    fn same_as_std() {
+        // skewed keys, interpolation guesses badly but must still be right
+        let nums: Vec<u32> = (0..1000).map(|i| i * i / 7).collect();
+        for target in 0..(1000 * 1000 / 7 + 2) {
+            if target % 97 != 0 && !(0..200).contains(&target) {
+                continue;
+            }
+            match (
+                interpolation_search(&nums, &target),
+                nums.binary_search(&target),
+            ) {
+                (Ok(i), Ok(_)) => assert_eq!(nums[i], target),
+                (found, expected) => assert_eq!(found, expected, "target {}", target),
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::cmp::Ordering;
+
+pub fn is_sorted<T: Ord>(nums: &[T]) -> bool {
+    is_sorted_by(nums, T::cmp)
+}
+
+// true if no element compares greater than the one after it
+pub fn is_sorted_by<T, F>(nums: &[T], mut compare: F) -> bool
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    nums.windows(2)
+        .all(|pair| compare(&pair[0], &pair[1]) != Ordering::Greater)
+}
+
+pub fn is_sorted_by_key<T, K, F>(nums: &[T], mut f: F) -> bool
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    is_sorted_by(nums, |a, b| f(a).cmp(&f(b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        assert!(is_sorted(&[1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]));
+        assert!(!is_sorted(&[6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5]));
+        assert!(is_sorted::<i32>(&[]));
+        assert!(is_sorted(&[1]));
+    }
+
+    #[test]
+    fn by() {
+        assert!(is_sorted_by(&[9, 8, 7, 7, 1], |a, b| b.cmp(a)));
+        assert!(!is_sorted_by(&[1, 2], |a, b| b.cmp(a)));
+
+        assert!(is_sorted_by_key(&["fig", "pear", "kiwi", "apple"], |w| w.len()));
+        assert!(!is_sorted_by_key(&["pear", "fig"], |w| w.len()));
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    fmt::Debug,
+};
+
+use crate::{sort::merge_sort, tree::max_heap::MaxHeap};
+
+// Merge two sorted slices into a new sorted vector, on ties the elements
+// of `a` come first.
+pub fn merge_sorted<T: Ord + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    merge_sorted_by(a, b, T::cmp)
+}
+
+pub fn merge_sorted_by<T, F>(a: &[T], b: &[T], mut compare: F) -> Vec<T>
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut merged = Vec::with_capacity(a.len() + b.len());
+    merged.extend_from_slice(a);
+    merged.extend_from_slice(b);
+    if a.is_empty() || b.is_empty() {
+        return merged;
+    }
+
+    // the two runs now sit side by side, as merge sort leaves them
+    let n = merged.len();
+    merge_sort::merge(&mut merged, 0, a.len() - 1, n - 1, &mut compare);
+    merged
+}
+
+pub fn merge_sorted_by_key<T, K, F>(a: &[T], b: &[T], mut f: F) -> Vec<T>
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    merge_sorted_by(a, b, |x, y| f(x).cmp(&f(y)))
+}
+
+// Heap entry, ties go to the earlier source to keep the merge stable.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Head<T> {
+    item: T,
+    source: usize,
+}
+
+// Lazily merges sorted iterators into one sorted iterator.
+pub struct KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord + Clone + Debug,
+{
+    sources: Vec<I>,
+    // the max heap turns into a min heap by reversing the order
+    heap: MaxHeap<Reverse<Head<I::Item>>>,
+}
+
+// k-way merge of sorted iterators, on ties items of earlier iterators come
+// first
+pub fn kmerge<I>(iters: I) -> KMerge<<I::Item as IntoIterator>::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::Item: Ord + Clone + Debug,
+{
+    let mut sources: Vec<_> = iters.into_iter().map(IntoIterator::into_iter).collect();
+
+    let mut heap = MaxHeap::new();
+    for (source, iter) in sources.iter_mut().enumerate() {
+        if let Some(item) = iter.next() {
+            heap.push(Reverse(Head { item, source }));
+        }
+    }
+
+    KMerge { sources, heap }
+}
+
+impl<I> Iterator for KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord + Clone + Debug,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let Reverse(Head { item, source }) = self.heap.pop()?;
+        if let Some(next) = self.sources[source].next() {
+            self.heap.push(Reverse(Head { item: next, source }));
+        }
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let a = vec![1, 2, 3, 4, 6, 9];
+        let b = vec![2, 3, 4, 5, 7, 8];
+
+        assert_eq!(
+            merge_sorted(&a, &b),
+            vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]
+        );
+        assert_eq!(merge_sorted(&a, &[]), a);
+        assert_eq!(merge_sorted(&[], &b), b);
+        assert_eq!(merge_sorted::<i32>(&[], &[]), vec![]);
+    }
+
+    #[test]
+    fn by() {
+        let a = vec![9, 6, 4, 3, 2, 1];
+        let b = vec![8, 7, 5, 4, 3, 2];
+        assert_eq!(
+            merge_sorted_by(&a, &b, |x, y| y.cmp(x)),
+            vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]
+        );
+
+        // ties keep `a` first
+        let a = vec!["fig", "kiwi", "apple"];
+        let b = vec!["pear", "melon"];
+        assert_eq!(
+            merge_sorted_by_key(&a, &b, |w| w.len()),
+            vec!["fig", "kiwi", "pear", "apple", "melon"]
+        );
+    }
+
+    #[test]
+    fn k_way() {
+        let runs = vec![
+            vec![1, 4, 7, 10],
+            vec![2, 5, 8],
+            vec![],
+            vec![3, 6, 9, 11, 12],
+        ];
+        let merged: Vec<i32> = kmerge(runs).collect();
+        assert_eq!(merged, (1..=12).collect::<Vec<_>>());
+
+        // (key, source) pairs, equal keys come out in source order
+        let runs = (0..4).map(|source| (0..50).map(move |i| (i / 5, source)));
+        let merged: Vec<(i32, i32)> = kmerge(runs).collect();
+        let mut expected = merged.clone();
+        expected.sort();
+        assert_eq!(merged, expected);
+
+        assert_eq!(kmerge(Vec::<Vec<i32>>::new()).next(), None);
+    }
+}
@@ -4,5 +4,20 @@ pub mod insert_sort;
 pub mod quick_sort;
 pub mod merge_sort;
 pub mod heap_sort;
+pub mod intro_sort;
+pub mod tim_sort;
 pub mod counting_sort;
 pub mod bucket_sort;
+pub mod radix_sort;
+pub mod parallel;
+pub mod external;
+pub mod stats;
+pub mod select;
+pub mod shell_sort;
+pub mod cocktail_sort;
+pub mod comb_sort;
+pub mod pancake_sort;
+pub mod cycle_sort;
+pub mod network;
+#[cfg(test)]
+mod harness;
@@ -1,12 +1,36 @@
+use std::cmp::Ordering;
+
+use super::stats::{self, SortObserver};
+
 pub fn bubble_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    bubble_sort_slice(&mut nums);
+    nums
+}
+
+pub fn bubble_sort_slice<T: Ord>(nums: &mut [T]) {
+    bubble_sort_by(nums, T::cmp);
+}
+
+pub fn bubble_sort_by<T, F>(nums: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    bubble_sort_observed(nums, compare, &mut ());
+}
+
+pub fn bubble_sort_observed<T, F, O>(nums: &mut [T], mut compare: F, observer: &mut O)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
     let n = nums.len();
 
     // [0, i] will be process
     for i in (1..n).rev() {
         let mut exchanged = false;
         for j in 0..i {
-            if nums[j] > nums[j + 1] {
-                (nums[j], nums[j + 1]) = (nums[j + 1], nums[j]);
+            if stats::compare(&mut compare, observer, &nums[j], &nums[j + 1]) == Ordering::Greater {
+                stats::swap(nums, j, j + 1, observer);
                 exchanged = true;
             }
         }
@@ -14,8 +38,14 @@ pub fn bubble_sort(mut nums: Vec<i32>) -> Vec<i32> {
             break;
         }
     }
+}
 
-    nums
+pub fn bubble_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    bubble_sort_by(nums, |a, b| f(a).cmp(&f(b)));
 }
 
 #[cfg(test)]
@@ -34,4 +64,24 @@ mod tests {
         let nums = bubble_sort(nums);
         assert_eq!(nums, vec![]);
     }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        bubble_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        bubble_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words, vec!["fig", "pear", "kiwi", "apple", "banana"]);
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        bubble_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
 }
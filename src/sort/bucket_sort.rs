@@ -1,26 +1,118 @@
-// input range [0, 1)
+use std::{error::Error, fmt};
+
+use super::{insert_sort::insert_sort_by, intro_sort::intro_sort_by};
+
+// buckets longer than this are considered skewed and sorted by intro sort
+const INSERT_THRES: usize = 16;
+
+// where NaNs end up in the sorted output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NanPolicy {
+    First,
+    #[default]
+    Last,
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BucketSortOptions {
+    // number of buckets, half the input length by default
+    pub buckets: Option<usize>,
+    pub nan_policy: NanPolicy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BucketSortError {
+    // NaN found at `index` under `NanPolicy::Reject`
+    Nan { index: usize },
+}
+
+impl fmt::Display for BucketSortError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BucketSortError::Nan { index } => write!(f, "NaN at index {}", index),
+        }
+    }
+}
+
+impl Error for BucketSortError {}
+
 pub fn bucket_sort(mut nums: Vec<f64>) -> Vec<f64> {
-    let k = nums.len() / 2;
+    bucket_sort_slice(&mut nums);
+    nums
+}
 
-    let mut buckets = vec![vec![]; k];
-    for &num in &nums {
-        let i = (num * k as f64) as usize;
-        buckets[i].push(num);
+// NaNs go last
+pub fn bucket_sort_slice(nums: &mut [f64]) {
+    bucket_sort_with(nums, BucketSortOptions::default()).unwrap();
+}
+
+// Buckets evenly split the [min, max] range of the finite inputs, infinities
+// land in the first or last bucket.
+pub fn bucket_sort_with(
+    nums: &mut [f64],
+    options: BucketSortOptions,
+) -> Result<(), BucketSortError> {
+    let n = nums.len();
+
+    if options.nan_policy == NanPolicy::Reject {
+        if let Some(index) = nums.iter().position(|num| num.is_nan()) {
+            return Err(BucketSortError::Nan { index });
+        }
     }
 
-    for bucket in &mut buckets {
-        bucket.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut min_max = None;
+    for &num in nums.iter().filter(|num| num.is_finite()) {
+        min_max = match min_max {
+            None => Some((num, num)),
+            Some((min, max)) => Some((num.min(min), num.max(max))),
+        };
+    }
+    let (min, max) = min_max.unwrap_or((0.0, 0.0));
+
+    let k = options.buckets.unwrap_or(n / 2).max(1);
+    // halved so that max - min cannot overflow to infinity
+    let width = max / 2.0 - min / 2.0;
+    let index = |num: f64| {
+        if width == 0.0 {
+            if num > max {
+                k - 1
+            } else {
+                0
+            }
+        } else {
+            // saturating cast sends -inf to 0 and +inf to usize::MAX
+            (((num / 2.0 - min / 2.0) / width * k as f64) as usize).min(k - 1)
+        }
+    };
+
+    let mut nans = vec![];
+    let mut buckets = vec![vec![]; k];
+    for &num in nums.iter() {
+        if num.is_nan() {
+            nans.push(num);
+        } else {
+            buckets[index(num)].push(num);
+        }
     }
 
-    let mut i = 0;
     for bucket in &mut buckets {
-        for &mut num in bucket {
-            nums[i] = num;
-            i += 1;
+        if bucket.len() > INSERT_THRES {
+            intro_sort_by(bucket, f64::total_cmp);
+        } else {
+            insert_sort_by(bucket, f64::total_cmp);
         }
     }
 
-    nums
+    let sorted = buckets.into_iter().flatten();
+    let result: Vec<f64> = if options.nan_policy == NanPolicy::First {
+        nans.into_iter().chain(sorted).collect()
+    } else {
+        sorted.chain(nans).collect()
+    };
+    nums.copy_from_slice(&result);
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -42,4 +134,90 @@ mod tests {
         let nums = bucket_sort(nums);
         assert_eq!(nums, vec![]);
     }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![0.6, 0.4, 0.2, 0.9, 0.4, 0.8, 0.1, 0.3, 0.7, 0.2, 0.3, 0.5];
+
+        bucket_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(
+            nums,
+            vec![0.6, 0.4, 0.2, 0.1, 0.3, 0.4, 0.7, 0.8, 0.9, 0.2, 0.3, 0.5]
+        );
+    }
+
+    #[test]
+    fn range() {
+        assert_eq!(bucket_sort(vec![1.0]), vec![1.0]);
+        assert_eq!(bucket_sort(vec![1.0, 0.5]), vec![0.5, 1.0]);
+        assert_eq!(bucket_sort(vec![2.0, 2.0, 2.0]), vec![2.0, 2.0, 2.0]);
+
+        let nums = vec![-3.5, 120.0, 0.0, -0.0, 7.25, -1e9, 1.0, 42.0];
+        assert_eq!(
+            bucket_sort(nums),
+            vec![-1e9, -3.5, -0.0, 0.0, 1.0, 7.25, 42.0, 120.0]
+        );
+
+        let nums = vec![f64::MAX, f64::INFINITY, -f64::MAX, f64::NEG_INFINITY, 0.0];
+        assert_eq!(
+            bucket_sort(nums),
+            vec![f64::NEG_INFINITY, -f64::MAX, 0.0, f64::MAX, f64::INFINITY]
+        );
+
+        let nums = vec![f64::INFINITY, 1.0, f64::NEG_INFINITY];
+        assert_eq!(
+            bucket_sort(nums),
+            vec![f64::NEG_INFINITY, 1.0, f64::INFINITY]
+        );
+    }
+
+    #[test]
+    fn options() {
+        // one huge outlier pushes everything else into the first bucket
+        let mut nums: Vec<f64> = (0..100).map(|i| ((i * 37) % 100) as f64).collect();
+        nums.push(1e12);
+        let mut expected = nums.clone();
+        expected.sort_by(f64::total_cmp);
+
+        let options = BucketSortOptions {
+            buckets: Some(3),
+            ..Default::default()
+        };
+        bucket_sort_with(&mut nums, options).unwrap();
+        assert_eq!(nums, expected);
+    }
+
+    #[test]
+    fn nan() {
+        let nums = vec![0.5, f64::NAN, -1.0, 3.0];
+
+        let mut first = nums.clone();
+        let options = BucketSortOptions {
+            nan_policy: NanPolicy::First,
+            ..Default::default()
+        };
+        bucket_sort_with(&mut first, options).unwrap();
+        assert!(first[0].is_nan());
+        assert_eq!(first[1..], [-1.0, 0.5, 3.0]);
+
+        let last = bucket_sort(nums.clone());
+        assert!(last[3].is_nan());
+        assert_eq!(last[..3], [-1.0, 0.5, 3.0]);
+
+        let mut rejected = nums.clone();
+        let options = BucketSortOptions {
+            nan_policy: NanPolicy::Reject,
+            ..Default::default()
+        };
+        assert_eq!(
+            bucket_sort_with(&mut rejected, options),
+            Err(BucketSortError::Nan { index: 1 })
+        );
+        assert_eq!(rejected[..1], [0.5]);
+        assert!(rejected[1].is_nan());
+
+        let all_nan = bucket_sort(vec![f64::NAN, f64::NAN]);
+        assert!(all_nan.iter().all(|num| num.is_nan()));
+    }
 }
@@ -0,0 +1,90 @@
+use std::cmp::Ordering;
+
+pub fn cocktail_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    cocktail_sort_slice(&mut nums);
+    nums
+}
+
+pub fn cocktail_sort_slice<T: Ord>(nums: &mut [T]) {
+    cocktail_sort_by(nums, T::cmp);
+}
+
+// bubble sort passing forward and backward in turn
+pub fn cocktail_sort_by<T, F>(nums: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if nums.len() < 2 {
+        return;
+    }
+
+    // [left, right] will be process
+    let (mut left, mut right) = (0, nums.len() - 1);
+    while left < right {
+        // bubble the max up to right
+        let mut last_swap = left;
+        for j in left..right {
+            if compare(&nums[j], &nums[j + 1]) == Ordering::Greater {
+                nums.swap(j, j + 1);
+                last_swap = j;
+            }
+        }
+        right = last_swap;
+
+        // bubble the min down to left
+        let mut first_swap = right;
+        for j in (left..right).rev() {
+            if compare(&nums[j], &nums[j + 1]) == Ordering::Greater {
+                nums.swap(j, j + 1);
+                first_swap = j + 1;
+            }
+        }
+        left = first_swap;
+    }
+}
+
+pub fn cocktail_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    cocktail_sort_by(nums, |a, b| f(a).cmp(&f(b)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        let nums = cocktail_sort(nums);
+
+        assert_eq!(nums, vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let nums = vec![];
+        let nums = cocktail_sort(nums);
+        assert_eq!(nums, vec![]);
+    }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        cocktail_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        cocktail_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words, vec!["fig", "pear", "kiwi", "apple", "banana"]);
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        cocktail_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
+}
@@ -1,34 +1,111 @@
-pub fn counting_sort(mut nums: Vec<i32>) -> Vec<i32> {
-    let mut max = None;
+use std::{error::Error, fmt, mem};
+
+use super::radix_sort::radix_sort_slice;
 
-    for &i in &nums {
-        if max.is_none() || i > max.unwrap() as i32 {
-            max = Some(i as usize);
+// default memory budget for the counter array, in bytes
+pub const DEFAULT_BUDGET: usize = 64 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CountingSortError {
+    // `range` counters would not fit into `budget` bytes
+    RangeTooLarge { range: u64, budget: usize },
+}
+
+impl fmt::Display for CountingSortError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CountingSortError::RangeTooLarge { range, budget } => write!(
+                f,
+                "key range of {} needs more than the {} bytes budget",
+                range, budget
+            ),
         }
     }
+}
 
-    if let Some(max) = max {
-        let mut counter = vec![0; max + 1];
-        for &i in &nums {
-            counter[i as usize] += 1;
-        }
+impl Error for CountingSortError {}
 
-        for i in 0..max {
-            counter[i + 1] += counter[i];
-        }
+// Keys too spread out for the default budget are radix sorted instead, so
+// this never fails, see `counting_sort_slice` for the strict version.
+pub fn counting_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    if counting_sort_slice(&mut nums).is_err() {
+        radix_sort_slice(&mut nums);
+    }
+    nums
+}
 
-        let mut result = vec![0; nums.len()];
-        for &num in nums.iter().rev() {
-            result[counter[num as usize] as usize - 1] = num;
-            counter[num as usize] -= 1;
-        }
+// leaves `nums` untouched if the keys need more than the default budget
+pub fn counting_sort_slice(nums: &mut [i32]) -> Result<(), CountingSortError> {
+    counting_sort_by_key(nums, |&num| num as i64)
+}
 
-        for (i, n) in result.into_iter().enumerate() {
-            nums[i] = n;
-        }
+pub fn counting_sort_by_key<T, F>(nums: &mut [T], key: F) -> Result<(), CountingSortError>
+where
+    T: Clone,
+    F: Fn(&T) -> i64,
+{
+    counting_sort_by_key_with_budget(nums, key, DEFAULT_BUDGET)
+}
+
+// Stable sort of `nums` by `key`, the counter array spans [min, max] of the
+// keys and may take at most `budget` bytes.
+pub fn counting_sort_by_key_with_budget<T, F>(
+    nums: &mut [T],
+    key: F,
+    budget: usize,
+) -> Result<(), CountingSortError>
+where
+    T: Clone,
+    F: Fn(&T) -> i64,
+{
+    let mut min_max = None;
+    for num in nums.iter() {
+        let k = key(num);
+        min_max = match min_max {
+            None => Some((k, k)),
+            Some((min, max)) => Some((k.min(min), k.max(max))),
+        };
     }
 
-    nums
+    let Some((min, max)) = min_max else {
+        return Ok(());
+    };
+
+    let range = (max.wrapping_sub(min) as u64).saturating_add(1);
+    if range > (budget / mem::size_of::<usize>()) as u64 {
+        return Err(CountingSortError::RangeTooLarge { range, budget });
+    }
+
+    let src = nums.to_vec();
+    counting_pass(&src, nums, range as usize, |num| {
+        key(num).wrapping_sub(min) as u64 as usize
+    });
+    Ok(())
+}
+
+// Stable distribution of `src` into `dst` by `key`, which must be below
+// `buckets`. Returns the start offset of every bucket in `dst`.
+pub(crate) fn counting_pass<T, F>(src: &[T], dst: &mut [T], buckets: usize, key: F) -> Vec<usize>
+where
+    T: Clone,
+    F: Fn(&T) -> usize,
+{
+    let mut counter = vec![0; buckets];
+    for num in src {
+        counter[key(num)] += 1;
+    }
+
+    for i in 0..buckets - 1 {
+        counter[i + 1] += counter[i];
+    }
+
+    for num in src.iter().rev() {
+        let k = key(num);
+        dst[counter[k] - 1] = num.clone();
+        counter[k] -= 1;
+    }
+
+    counter
 }
 
 #[cfg(test)]
@@ -47,4 +124,80 @@ mod tests {
         let nums = counting_sort(nums);
         assert_eq!(nums, vec![]);
     }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        counting_sort_slice(&mut nums[3..9]).unwrap();
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
+
+    #[test]
+    fn negative() {
+        let nums = vec![6, -4, 2, -9, 0, 8, -1, 3, 7, -2, 3, 5];
+
+        let nums = counting_sort(nums);
+
+        assert_eq!(nums, vec![-9, -4, -2, -1, 0, 2, 3, 3, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn too_wide() {
+        // far too wide for a counter array, the slice version refuses
+        let mut nums = vec![i32::MAX, 0, i32::MIN, -1];
+        assert_eq!(
+            counting_sort_slice(&mut nums),
+            Err(CountingSortError::RangeTooLarge {
+                range: 1 << 32,
+                budget: DEFAULT_BUDGET
+            })
+        );
+        assert_eq!(nums, vec![i32::MAX, 0, i32::MIN, -1]);
+
+        // and the vec version falls back to radix sort
+        let nums = counting_sort(nums);
+        assert_eq!(nums, vec![i32::MIN, -1, 0, i32::MAX]);
+    }
+
+    #[test]
+    fn by_key() {
+        let mut records = vec![(3, 'a'), (-1, 'b'), (3, 'c'), (0, 'd'), (-1, 'e'), (2, 'f')];
+
+        counting_sort_by_key(&mut records, |r| r.0).unwrap();
+
+        assert_eq!(
+            records,
+            vec![(-1, 'b'), (-1, 'e'), (0, 'd'), (2, 'f'), (3, 'a'), (3, 'c')]
+        );
+    }
+
+    #[test]
+    fn budget() {
+        let mut nums = vec![0i64, 1000, 500];
+        let budget = 100 * mem::size_of::<usize>();
+
+        assert_eq!(
+            counting_sort_by_key_with_budget(&mut nums, |&n| n, budget),
+            Err(CountingSortError::RangeTooLarge {
+                range: 1001,
+                budget
+            })
+        );
+        assert_eq!(nums, vec![0, 1000, 500]);
+
+        let mut nums = vec![i64::MAX, i64::MIN];
+        assert_eq!(
+            counting_sort_by_key(&mut nums, |&n| n),
+            Err(CountingSortError::RangeTooLarge {
+                range: u64::MAX,
+                budget: DEFAULT_BUDGET
+            })
+        );
+
+        let mut nums = vec![1000i64, 950, 999];
+        counting_sort_by_key_with_budget(&mut nums, |&n| n, budget).unwrap();
+        assert_eq!(nums, vec![950, 999, 1000]);
+    }
 }
@@ -0,0 +1,161 @@
+use std::{cmp::Ordering, mem};
+
+use super::stats::{self, SortObserver};
+
+pub fn cycle_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    cycle_sort_slice(&mut nums);
+    nums
+}
+
+pub fn cycle_sort_slice<T: Ord + Clone>(nums: &mut [T]) {
+    cycle_sort_by(nums, T::cmp);
+}
+
+pub fn cycle_sort_by<T, F>(nums: &mut [T], compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    cycle_sort_observed(nums, compare, &mut ());
+}
+
+// Follows each cycle of the permutation holding the displaced element, so
+// every element is written once, straight into its final slot, and one
+// already in place is never written at all.
+pub fn cycle_sort_observed<T, F, O>(nums: &mut [T], mut compare: F, observer: &mut O)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    let n = nums.len();
+
+    for start in 0..n.saturating_sub(1) {
+        let mut pos = position(nums, start, &nums[start], &mut compare, observer);
+        if pos == start {
+            continue;
+        }
+
+        let mut item = nums[start].clone();
+        while pos != start {
+            mem::swap(&mut item, &mut nums[pos]);
+            observer.moves(1, nums);
+            pos = position(nums, start, &item, &mut compare, observer);
+        }
+        // the cycle closed, nums[start] only held a stale copy
+        nums[start] = item;
+        observer.moves(1, nums);
+    }
+}
+
+// Where `item` goes: after everything in `nums[start + 1..]` less than it,
+// and after the elements equal to it already put there.
+fn position<T, F, O>(nums: &[T], start: usize, item: &T, compare: &mut F, observer: &mut O) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    let mut pos = start;
+    for num in &nums[start + 1..] {
+        if stats::compare(compare, observer, num, item) == Ordering::Less {
+            pos += 1;
+        }
+    }
+    if pos == start {
+        return pos;
+    }
+    while stats::compare(compare, observer, &nums[pos], item) == Ordering::Equal {
+        pos += 1;
+    }
+    pos
+}
+
+pub fn cycle_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    cycle_sort_by(nums, |a, b| f(a).cmp(&f(b)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::stats::SortStats;
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        let nums = cycle_sort(nums);
+
+        assert_eq!(nums, vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let nums = vec![];
+        let nums = cycle_sort(nums);
+        assert_eq!(nums, vec![]);
+    }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        cycle_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        cycle_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "apple");
+        assert_eq!(words[4], "banana");
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        cycle_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
+
+    #[test]
+    fn duplicates() {
+        let mut nums: Vec<i32> = (0..500).map(|i| (i * 7919) % 13).collect();
+        let mut expected = nums.clone();
+        expected.sort();
+
+        cycle_sort_slice(&mut nums);
+
+        assert_eq!(nums, expected);
+    }
+
+    #[test]
+    fn writes() {
+        // a permutation of 0..1000, each misplaced element is written once
+        let mut nums: Vec<i32> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+        let misplaced = nums
+            .iter()
+            .enumerate()
+            .filter(|&(i, &num)| i as i32 != num)
+            .count();
+        let mut stats = SortStats::new();
+        cycle_sort_observed(&mut nums, i32::cmp, &mut stats);
+        assert_eq!(nums, (0..1000).collect::<Vec<_>>());
+        assert_eq!(stats.moves, misplaced);
+        assert!(stats.moves <= nums.len());
+        assert_eq!(stats.swaps, 0);
+
+        // sorted input isn't written at all
+        let mut stats = SortStats::new();
+        cycle_sort_observed(&mut nums, i32::cmp, &mut stats);
+        assert_eq!(stats.moves, 0);
+
+        // with duplicates as well
+        let mut nums: Vec<i32> = (0..500).map(|i| (i * 7919) % 13).collect();
+        let mut stats = SortStats::new();
+        cycle_sort_observed(&mut nums, i32::cmp, &mut stats);
+        assert!(nums.windows(2).all(|w| w[0] <= w[1]));
+        assert!(stats.moves <= nums.len());
+    }
+}
@@ -0,0 +1,357 @@
+use std::{
+    cmp::Reverse,
+    env,
+    fmt::Debug,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    marker::PhantomData,
+    mem,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use super::tim_sort::tim_sort_slice;
+use crate::tree::max_heap::MaxHeap;
+
+// How records are read from and written to the input, output and run files.
+pub trait Codec {
+    type Record;
+
+    // `Ok(None)` at a clean end of input
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>;
+
+    fn write<W: Write>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()>;
+
+    // bytes a record takes in memory, counted against the memory budget
+    fn size(&self, _record: &Self::Record) -> usize {
+        mem::size_of::<Self::Record>()
+    }
+}
+
+// little endian fixed width integers
+#[derive(Debug)]
+pub struct FixedWidth<T>(PhantomData<T>);
+
+impl<T> FixedWidth<T> {
+    pub fn new() -> Self {
+        FixedWidth(PhantomData)
+    }
+}
+
+impl<T> Default for FixedWidth<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! fixed_width_codec {
+    ($($t:ty),*) => {$(
+        impl Codec for FixedWidth<$t> {
+            type Record = $t;
+
+            fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<$t>> {
+                if reader.fill_buf()?.is_empty() {
+                    return Ok(None);
+                }
+                let mut bytes = [0; mem::size_of::<$t>()];
+                reader.read_exact(&mut bytes)?;
+                Ok(Some(<$t>::from_le_bytes(bytes)))
+            }
+
+            fn write<W: Write>(&self, writer: &mut W, record: &$t) -> io::Result<()> {
+                writer.write_all(&record.to_le_bytes())
+            }
+        }
+    )*};
+}
+
+fixed_width_codec!(u32, u64, i32, i64);
+
+// newline delimited text records, the newline is not part of the record
+#[derive(Debug, Default)]
+pub struct Lines;
+
+impl Codec for Lines {
+    type Record = String;
+
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if line.ends_with('\n') {
+            line.pop();
+        }
+        Ok(Some(line))
+    }
+
+    fn write<W: Write>(&self, writer: &mut W, record: &String) -> io::Result<()> {
+        writer.write_all(record.as_bytes())?;
+        writer.write_all(b"\n")
+    }
+
+    fn size(&self, record: &String) -> usize {
+        mem::size_of::<String>() + record.capacity()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExternalSortOptions {
+    // bytes of records held in memory before a run is spilled
+    pub memory_budget: usize,
+    // where run files are created
+    pub temp_dir: PathBuf,
+    // maximum number of runs merged at once
+    pub fan_in: usize,
+}
+
+impl Default for ExternalSortOptions {
+    fn default() -> Self {
+        Self {
+            memory_budget: 64 << 20,
+            temp_dir: env::temp_dir(),
+            fan_in: 64,
+        }
+    }
+}
+
+// A sorted run spilled to disk, the file is removed on drop.
+struct Run {
+    path: PathBuf,
+}
+
+impl Run {
+    fn create(temp_dir: &Path) -> io::Result<(Run, BufWriter<File>)> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = temp_dir.join(format!("dsa_in_rust-{}-{}.run", process::id(), id));
+        let file = File::create(&path)?;
+        Ok((Run { path }, BufWriter::new(file)))
+    }
+
+    fn open(&self) -> io::Result<BufReader<File>> {
+        Ok(BufReader::new(File::open(&self.path)?))
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// Heap entry, ties go to the earlier run to keep the sort stable.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Head<T> {
+    record: T,
+    run: usize,
+}
+
+// Stable sort of every record of `input` into `output`.
+pub fn external_sort<C, R, W>(
+    input: R,
+    output: W,
+    codec: &C,
+    options: &ExternalSortOptions,
+) -> io::Result<()>
+where
+    C: Codec,
+    C::Record: Ord + Clone + Debug,
+    R: Read,
+    W: Write,
+{
+    let mut reader = BufReader::new(input);
+    let mut writer = BufWriter::new(output);
+
+    let mut runs = vec![];
+    let mut chunk = vec![];
+    let mut used = 0;
+    while let Some(record) = codec.read(&mut reader)? {
+        used += codec.size(&record);
+        chunk.push(record);
+        if used >= options.memory_budget {
+            runs.push(spill(&mut chunk, codec, options)?);
+            used = 0;
+        }
+    }
+
+    // everything fit into memory, no need to touch the disk
+    if runs.is_empty() {
+        tim_sort_slice(&mut chunk);
+        for record in &chunk {
+            codec.write(&mut writer, record)?;
+        }
+        return writer.flush();
+    }
+
+    if !chunk.is_empty() {
+        runs.push(spill(&mut chunk, codec, options)?);
+    }
+
+    // merge groups of neighbouring runs until one pass can take them all
+    let fan_in = options.fan_in.max(2);
+    while runs.len() > fan_in {
+        let mut merged = vec![];
+        for group in runs.chunks(fan_in) {
+            let (run, mut run_writer) = Run::create(&options.temp_dir)?;
+            merge_runs(group, codec, &mut run_writer)?;
+            run_writer.flush()?;
+            merged.push(run);
+        }
+        runs = merged;
+    }
+
+    merge_runs(&runs, codec, &mut writer)?;
+    writer.flush()
+}
+
+fn spill<C>(chunk: &mut Vec<C::Record>, codec: &C, options: &ExternalSortOptions) -> io::Result<Run>
+where
+    C: Codec,
+    C::Record: Ord + Clone,
+{
+    tim_sort_slice(chunk);
+
+    let (run, mut writer) = Run::create(&options.temp_dir)?;
+    for record in chunk.drain(..) {
+        codec.write(&mut writer, &record)?;
+    }
+    writer.flush()?;
+    Ok(run)
+}
+
+// k-way merge, the max heap turns into a min heap by reversing the order
+fn merge_runs<C, W>(runs: &[Run], codec: &C, writer: &mut W) -> io::Result<()>
+where
+    C: Codec,
+    C::Record: Ord + Clone + Debug,
+    W: Write,
+{
+    let mut readers = runs.iter().map(Run::open).collect::<io::Result<Vec<_>>>()?;
+
+    let mut heap = MaxHeap::new();
+    for (run, reader) in readers.iter_mut().enumerate() {
+        if let Some(record) = codec.read(reader)? {
+            heap.push(Reverse(Head { record, run }));
+        }
+    }
+
+    while let Some(Reverse(Head { record, run })) = heap.pop() {
+        codec.write(writer, &record)?;
+        if let Some(record) = codec.read(&mut readers[run])? {
+            heap.push(Reverse(Head { record, run }));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("dsa_in_rust-test-{}-{}", process::id(), name));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn fixed_width() {
+        let nums: Vec<i64> = (0..1000).map(|i| (i * 7919 % 1009) - 500).collect();
+        let mut input = vec![];
+        for num in &nums {
+            input.extend_from_slice(&num.to_le_bytes());
+        }
+
+        let dir = temp_dir("fixed_width");
+        let options = ExternalSortOptions {
+            memory_budget: 100 * mem::size_of::<i64>(),
+            temp_dir: dir.clone(),
+            fan_in: 3,
+        };
+        let mut output = vec![];
+        external_sort(
+            Cursor::new(input),
+            &mut output,
+            &FixedWidth::<i64>::new(),
+            &options,
+        )
+        .unwrap();
+
+        let codec = FixedWidth::<i64>::new();
+        let mut reader = Cursor::new(output);
+        let mut sorted = vec![];
+        while let Some(num) = codec.read(&mut reader).unwrap() {
+            sorted.push(num);
+        }
+
+        let mut expected = nums;
+        expected.sort();
+        assert_eq!(sorted, expected);
+
+        // every run file is gone
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir(dir).unwrap();
+    }
+
+    #[test]
+    fn lines() {
+        let input = "pear\nfig\nbanana\n\nkiwi\napple\nfig\ncherry";
+
+        let dir = temp_dir("lines");
+        let options = ExternalSortOptions {
+            memory_budget: 2 * mem::size_of::<String>(),
+            temp_dir: dir.clone(),
+            ..Default::default()
+        };
+        let mut output = vec![];
+        external_sort(input.as_bytes(), &mut output, &Lines, &options).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\napple\nbanana\ncherry\nfig\nfig\nkiwi\npear\n"
+        );
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir(dir).unwrap();
+    }
+
+    #[test]
+    fn in_memory() {
+        let mut output = vec![];
+        external_sort(
+            "b\na\nc\n".as_bytes(),
+            &mut output,
+            &Lines,
+            &ExternalSortOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(output, b"a\nb\nc\n");
+
+        let mut output = vec![];
+        external_sort(
+            &[][..],
+            &mut output,
+            &FixedWidth::<u32>::new(),
+            &ExternalSortOptions::default(),
+        )
+        .unwrap();
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn truncated() {
+        let mut output = vec![];
+        let err = external_sort(
+            &[1, 0, 0][..],
+            &mut output,
+            &FixedWidth::<u32>::new(),
+            &ExternalSortOptions::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
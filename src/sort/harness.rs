@@ -0,0 +1,312 @@
+// Differential tests running every sort against `slice::sort` on generated
+// inputs. Inputs come from a seeded generator so a failure always reproduces.
+
+use std::cmp::Ordering;
+
+use super::{
+    bubble_sort, bucket_sort, cocktail_sort, comb_sort, counting_sort, cycle_sort, heap_sort,
+    insert_sort, intro_sort, merge_sort, pancake_sort, parallel,
+    quick_sort::{self, PartitionScheme, QuickSortOptions},
+    radix_sort, select, select_sort,
+    shell_sort::{self, GapSequence},
+    tim_sort,
+};
+
+const SEED: u64 = 0x5eed_d5a1;
+
+// quadratic sorts stop at this length
+const SMALL_LENS: [usize; 7] = [0, 1, 2, 3, 10, 100, 500];
+const LARGE_LEN: usize = 20000;
+
+// xorshift64*
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // the state must never be zero
+        Rng(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // uniform in [0, n), n must not be zero
+    fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Pattern {
+    AllEqual,
+    Sorted,
+    Reversed,
+    // ascending runs of length 16
+    Sawtooth,
+    Random,
+    // random over only a handful of keys, so lots of ties
+    FewUnique,
+}
+
+const PATTERNS: [Pattern; 6] = [
+    Pattern::AllEqual,
+    Pattern::Sorted,
+    Pattern::Reversed,
+    Pattern::Sawtooth,
+    Pattern::Random,
+    Pattern::FewUnique,
+];
+
+fn generate(pattern: Pattern, len: usize, rng: &mut Rng) -> Vec<i32> {
+    let len_i32 = len as i32;
+    (0..len_i32)
+        .map(|i| match pattern {
+            Pattern::AllEqual => 7,
+            Pattern::Sorted => i - len_i32 / 2,
+            Pattern::Reversed => len_i32 / 2 - i,
+            Pattern::Sawtooth => i % 16,
+            Pattern::Random => rng.below(2 * len as u64) as i32 - len_i32,
+            Pattern::FewUnique => rng.below(4) as i32,
+        })
+        .collect()
+}
+
+// Element remembering where it started, only `key` is compared.
+#[derive(Debug, Clone, PartialEq)]
+struct Tagged {
+    key: i32,
+    index: usize,
+}
+
+fn tag(keys: &[i32]) -> Vec<Tagged> {
+    keys.iter()
+        .enumerate()
+        .map(|(index, &key)| Tagged { key, index })
+        .collect()
+}
+
+struct Case {
+    name: &'static str,
+    stable: bool,
+    quadratic: bool,
+    sort: fn(&mut [Tagged]),
+}
+
+fn by_key(a: &Tagged, b: &Tagged) -> Ordering {
+    a.key.cmp(&b.key)
+}
+
+fn cases() -> Vec<Case> {
+    fn case(name: &'static str, stable: bool, quadratic: bool, sort: fn(&mut [Tagged])) -> Case {
+        Case {
+            name,
+            stable,
+            quadratic,
+            sort,
+        }
+    }
+
+    vec![
+        case("bubble", true, true, |nums| {
+            bubble_sort::bubble_sort_by(nums, by_key)
+        }),
+        case("select", false, true, |nums| {
+            select_sort::select_sort_by(nums, by_key)
+        }),
+        case("insert", true, true, |nums| {
+            insert_sort::insert_sort_by(nums, by_key)
+        }),
+        case("cocktail", true, true, |nums| {
+            cocktail_sort::cocktail_sort_by(nums, by_key)
+        }),
+        case("pancake", false, true, |nums| {
+            pancake_sort::pancake_sort_by(nums, by_key)
+        }),
+        case("cycle", false, true, |nums| {
+            cycle_sort::cycle_sort_by(nums, by_key)
+        }),
+        case("comb", false, false, |nums| {
+            comb_sort::comb_sort_by(nums, by_key)
+        }),
+        case("shell", false, false, |nums| {
+            let gaps = GapSequence::Shell.gaps(nums.len());
+            shell_sort::shell_sort_with_gaps(nums, &gaps, by_key)
+        }),
+        case("shell knuth", false, false, |nums| {
+            let gaps = GapSequence::Knuth.gaps(nums.len());
+            shell_sort::shell_sort_with_gaps(nums, &gaps, by_key)
+        }),
+        case("shell ciura", false, false, |nums| {
+            shell_sort::shell_sort_by(nums, by_key)
+        }),
+        case("shell sedgewick", false, false, |nums| {
+            let gaps = GapSequence::Sedgewick.gaps(nums.len());
+            shell_sort::shell_sort_with_gaps(nums, &gaps, by_key)
+        }),
+        case("heap", false, false, |nums| {
+            heap_sort::heap_sort_by(nums, by_key)
+        }),
+        case("merge", true, false, |nums| {
+            merge_sort::merge_sort_by(nums, by_key)
+        }),
+        // the two way partition is quadratic on all equal input
+        case("quick", false, true, |nums| {
+            quick_sort::quick_sort_by(nums, by_key)
+        }),
+        case("quick three way", false, false, |nums| {
+            let options = QuickSortOptions {
+                partition: PartitionScheme::ThreeWay,
+            };
+            quick_sort::quick_sort_with(nums, by_key, options)
+        }),
+        case("quick dual pivot", false, false, |nums| {
+            let options = QuickSortOptions {
+                partition: PartitionScheme::DualPivot,
+            };
+            quick_sort::quick_sort_with(nums, by_key, options)
+        }),
+        case("intro", false, false, |nums| {
+            intro_sort::intro_sort_by(nums, by_key)
+        }),
+        case("tim", true, false, |nums| {
+            tim_sort::tim_sort_by(nums, by_key)
+        }),
+        case("counting", true, false, |nums| {
+            counting_sort::counting_sort_by_key(nums, |num| num.key as i64).unwrap()
+        }),
+        case("parallel merge", true, false, |nums| {
+            parallel::merge_sort_by(nums, by_key)
+        }),
+        case("parallel quick", false, true, |nums| {
+            parallel::quick_sort_by(nums, by_key)
+        }),
+        case("partial", false, false, |nums| {
+            let n = nums.len();
+            select::partial_sort_by(nums, n, by_key)
+        }),
+    ]
+}
+
+fn check(case: &Case, pattern: Pattern, len: usize, rng: &mut Rng) {
+    let keys = generate(pattern, len, rng);
+    let input = tag(&keys);
+
+    let mut expected = input.clone();
+    expected.sort_by(by_key);
+
+    let mut nums = input;
+    (case.sort)(&mut nums);
+
+    let context = format!(
+        "{} sort on {:?} input of length {}",
+        case.name, pattern, len
+    );
+    if case.stable {
+        // std's sort is stable, so ties must keep their original order too
+        assert_eq!(nums, expected, "{}", context);
+        return;
+    }
+
+    let sorted: Vec<i32> = nums.iter().map(|num| num.key).collect();
+    let expected: Vec<i32> = expected.iter().map(|num| num.key).collect();
+    assert_eq!(sorted, expected, "{}", context);
+
+    // every original element is still there exactly once
+    let mut indices: Vec<usize> = nums.iter().map(|num| num.index).collect();
+    indices.sort();
+    assert!(indices.iter().copied().eq(0..len), "{}", context);
+}
+
+#[test]
+fn comparison_sorts() {
+    let mut rng = Rng::new(SEED);
+    for case in cases() {
+        for pattern in PATTERNS {
+            for len in SMALL_LENS {
+                check(&case, pattern, len, &mut rng);
+            }
+            if !case.quadratic {
+                check(&case, pattern, LARGE_LEN, &mut rng);
+            }
+        }
+    }
+}
+
+#[test]
+fn integer_sorts() {
+    type IntegerSort = fn(&mut [i32]);
+    let integer_sorts: [(&str, IntegerSort); 3] = [
+        ("radix", radix_sort::radix_sort_slice),
+        ("counting", |nums| {
+            counting_sort::counting_sort_slice(nums).unwrap()
+        }),
+        ("tim", tim_sort::tim_sort_slice),
+    ];
+
+    let mut rng = Rng::new(SEED);
+    for (name, sort) in integer_sorts {
+        for pattern in PATTERNS {
+            for len in SMALL_LENS.into_iter().chain([LARGE_LEN]) {
+                let mut nums = generate(pattern, len, &mut rng);
+                let mut expected = nums.clone();
+                expected.sort();
+
+                sort(&mut nums);
+
+                assert_eq!(
+                    nums, expected,
+                    "{} sort on {:?} input of length {}",
+                    name, pattern, len
+                );
+            }
+        }
+    }
+
+    // keys far enough apart to leave the counting range
+    let mut nums: Vec<i32> = (0..1000).map(|_| rng.next_u64() as i32).collect();
+    let mut expected = nums.clone();
+    expected.sort();
+    assert!(counting_sort::counting_sort_slice(&mut nums).is_err());
+    let nums = counting_sort::counting_sort(nums);
+    assert_eq!(nums, expected);
+}
+
+#[test]
+fn float_sorts() {
+    let mut rng = Rng::new(SEED);
+    for pattern in PATTERNS {
+        for len in SMALL_LENS.into_iter().chain([LARGE_LEN]) {
+            let mut nums: Vec<f64> = generate(pattern, len, &mut rng)
+                .into_iter()
+                .map(|num| num as f64 / 8.0)
+                .collect();
+            let mut expected = nums.clone();
+            expected.sort_by(f64::total_cmp);
+
+            bucket_sort::bucket_sort_slice(&mut nums);
+
+            assert_eq!(
+                nums, expected,
+                "bucket sort on {:?} input of length {}",
+                pattern, len
+            );
+        }
+    }
+}
+
+#[test]
+fn rng() {
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+    let first: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+    assert!((0..8).all(|i| b.next_u64() == first[i]));
+
+    let mut c = Rng::new(43);
+    assert_ne!(c.next_u64(), first[0]);
+
+    assert!((0..1000).all(|_| a.below(10) < 10));
+}
@@ -1,33 +1,70 @@
+use std::cmp::Ordering;
+
+use super::stats::{self, SortObserver};
+
 pub fn heap_sort(mut nums: Vec<i32>) -> Vec<i32> {
-    let n = nums.len() as i32;
+    heap_sort_slice(&mut nums);
+    nums
+}
+
+pub fn heap_sort_slice<T: Ord>(nums: &mut [T]) {
+    heap_sort_by(nums, T::cmp);
+}
 
-    for i in (0..=(n - 1) / 2).rev() {
-        sift_down(&mut nums, n as usize, i as usize);
+pub fn heap_sort_by<T, F>(nums: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    heap_sort_observed(nums, compare, &mut ());
+}
+
+pub fn heap_sort_observed<T, F, O>(nums: &mut [T], mut compare: F, observer: &mut O)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    let n = nums.len();
+    if n < 2 {
+        return;
+    }
+
+    for i in (0..=(n - 2) / 2).rev() {
+        sift_down(nums, n, i, &mut compare, observer);
     }
 
     for i in (1..n).rev() {
-        (nums[i as usize], nums[0]) = (nums[0], nums[i as usize]);
+        stats::swap(nums, i, 0, observer);
 
-        sift_down(&mut nums, i as usize, 0);
+        sift_down(nums, i, 0, &mut compare, observer);
     }
+}
 
-    nums
+pub fn heap_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    heap_sort_by(nums, |a, b| f(a).cmp(&f(b)));
 }
 
-fn sift_down(nums: &mut [i32], n: usize, mut i: usize) {
+fn sift_down<T, F, O>(nums: &mut [T], n: usize, mut i: usize, compare: &mut F, observer: &mut O)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
     loop {
         let (l, r, mut max) = (2 * i + 1, 2 * i + 2, i);
 
-        if l < n && nums[l] > nums[max] {
+        if l < n && stats::compare(compare, observer, &nums[l], &nums[max]) == Ordering::Greater {
             max = l;
         }
-        if r < n && nums[r] > nums[max] {
+        if r < n && stats::compare(compare, observer, &nums[r], &nums[max]) == Ordering::Greater {
             max = r;
         }
         if max == i {
             break;
         }
-        (nums[max], nums[i]) = (nums[i], nums[max]);
+        stats::swap(nums, max, i, observer);
         i = max;
     }
 }
@@ -48,4 +85,26 @@ mod tests {
         let nums = heap_sort(nums);
         assert_eq!(nums, vec![]);
     }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        heap_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        heap_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "apple");
+        assert_eq!(words[4], "banana");
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        heap_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
 }
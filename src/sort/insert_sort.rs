@@ -1,17 +1,48 @@
+use std::cmp::Ordering;
+
+use super::stats::{self, SortObserver};
+
 pub fn insert_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    insert_sort_slice(&mut nums);
+    nums
+}
+
+pub fn insert_sort_slice<T: Ord>(nums: &mut [T]) {
+    insert_sort_by(nums, T::cmp);
+}
+
+pub fn insert_sort_by<T, F>(nums: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    insert_sort_observed(nums, compare, &mut ());
+}
+
+pub fn insert_sort_observed<T, F, O>(nums: &mut [T], mut compare: F, observer: &mut O)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
     let n = nums.len();
 
     for i in 1..n {
-        let base = nums[i];
-        let mut j = (i - 1) as i32;
-        while j >= 0 && nums[j as usize] > base {
-            nums[(j + 1) as usize] = nums[j as usize];
+        // sink nums[i] down until it meets an element not greater than it
+        let mut j = i;
+        while j > 0
+            && stats::compare(&mut compare, observer, &nums[j - 1], &nums[j]) == Ordering::Greater
+        {
+            stats::swap(nums, j - 1, j, observer);
             j -= 1;
         }
-        nums[(j + 1) as usize] = base;
     }
+}
 
-    nums
+pub fn insert_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    insert_sort_by(nums, |a, b| f(a).cmp(&f(b)));
 }
 
 #[cfg(test)]
@@ -30,4 +61,24 @@ mod tests {
         let nums = insert_sort(nums);
         assert_eq!(nums, vec![]);
     }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        insert_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        insert_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words, vec!["fig", "pear", "kiwi", "apple", "banana"]);
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        insert_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
 }
@@ -0,0 +1,130 @@
+use std::cmp::Ordering;
+
+use super::{heap_sort::heap_sort_by, insert_sort::insert_sort_by, quick_sort::partition};
+
+// partitions at most this long are finished by insertion sort
+const INSERT_THRES: usize = 16;
+
+pub fn intro_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    intro_sort_slice(&mut nums);
+    nums
+}
+
+pub fn intro_sort_slice<T: Ord>(nums: &mut [T]) {
+    intro_sort_by(nums, T::cmp);
+}
+
+pub fn intro_sort_by<T, F>(nums: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if nums.len() < 2 {
+        return;
+    }
+    let depth_limit = 2 * nums.len().ilog2() as usize;
+    _intro_sort(nums, depth_limit, &mut compare);
+}
+
+pub fn intro_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    intro_sort_by(nums, |a, b| f(a).cmp(&f(b)));
+}
+
+fn _intro_sort<T, F>(mut nums: &mut [T], mut depth_limit: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    while nums.len() > INSERT_THRES {
+        // too many bad pivots, quick sort is going quadratic
+        if depth_limit == 0 {
+            heap_sort_by(nums, &mut *compare);
+            return;
+        }
+        depth_limit -= 1;
+
+        let pivot = partition(nums, 0, nums.len() - 1, compare);
+
+        let (left, right) = std::mem::take(&mut nums).split_at_mut(pivot);
+        let right = &mut right[1..];
+
+        if left.len() < right.len() {
+            _intro_sort(left, depth_limit, compare);
+            nums = right;
+        } else {
+            _intro_sort(right, depth_limit, compare);
+            nums = left;
+        }
+    }
+
+    insert_sort_by(nums, compare);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        let nums = intro_sort(nums);
+
+        assert_eq!(nums, vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let nums = vec![];
+        let nums = intro_sort(nums);
+        assert_eq!(nums, vec![]);
+    }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        intro_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        intro_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "apple");
+        assert_eq!(words[4], "banana");
+    }
+
+    #[test]
+    fn depth_limit() {
+        // with a depth limit of zero every partition falls back to heap sort,
+        // so the comparisons are exactly heap sort's
+        let input: Vec<i32> = (0..100).rev().chain(0..100).collect();
+        let mut nums = input.clone();
+        let mut intro_comparisons = 0;
+        _intro_sort(&mut nums, 0, &mut |a: &i32, b: &i32| {
+            intro_comparisons += 1;
+            a.cmp(b)
+        });
+        let mut expected = input.clone();
+        let mut heap_comparisons = 0;
+        heap_sort_by(&mut expected, |a, b| {
+            heap_comparisons += 1;
+            a.cmp(b)
+        });
+        assert_eq!(nums, expected);
+        assert_eq!(intro_comparisons, heap_comparisons);
+
+        // all equal input sends the two way partition quadratic, about n^2 / 2
+        // comparisons, unless the fallback cuts it off
+        let n = 10000;
+        let mut nums = vec![7; n];
+        let mut comparisons = 0;
+        intro_sort_by(&mut nums, |a, b| {
+            comparisons += 1;
+            a.cmp(b)
+        });
+        assert!(comparisons < 64 * n, "{} comparisons", comparisons);
+
+        let mut nums: Vec<i32> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+        intro_sort_slice(&mut nums);
+        assert_eq!(nums, (0..1000).collect::<Vec<_>>());
+    }
+}
@@ -1,54 +1,110 @@
+use std::cmp::Ordering;
+
+use super::stats::{self, SortObserver};
+
 pub fn merge_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    merge_sort_slice(&mut nums);
+    nums
+}
+
+pub fn merge_sort_slice<T: Ord + Clone>(nums: &mut [T]) {
+    merge_sort_by(nums, T::cmp);
+}
+
+pub fn merge_sort_by<T, F>(nums: &mut [T], compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    merge_sort_observed(nums, compare, &mut ());
+}
+
+pub fn merge_sort_observed<T, F, O>(nums: &mut [T], mut compare: F, observer: &mut O)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
     if nums.is_empty() {
-        return nums;
+        return;
     }
     let n = nums.len();
-    _merge_sort(&mut nums, 0, n - 1);
-    nums
+    _merge_sort(nums, 0, n - 1, &mut compare, observer);
 }
 
-fn _merge_sort(nums: &mut [i32], left: usize, right: usize) {
+pub fn merge_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    merge_sort_by(nums, |a, b| f(a).cmp(&f(b)));
+}
+
+fn _merge_sort<T, F, O>(
+    nums: &mut [T],
+    left: usize,
+    right: usize,
+    compare: &mut F,
+    observer: &mut O,
+) where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
     if left >= right {
         return;
     }
     let mid = left + (right - left) / 2;
-    _merge_sort(nums, left, mid);
-    _merge_sort(nums, mid + 1, right);
+    observer.enter();
+    _merge_sort(nums, left, mid, compare, observer);
+    _merge_sort(nums, mid + 1, right, compare, observer);
+    observer.leave();
 
-    merge(nums, left, mid, right);
+    merge_observed(nums, left, mid, right, compare, observer);
 }
 
-fn merge(nums: &mut [i32], left: usize, mid: usize, right: usize) {
-    let temp_size = right - left + 1;
-    let mut temp = vec![0; temp_size];
+pub(crate) fn merge<T, F>(nums: &mut [T], left: usize, mid: usize, right: usize, compare: &mut F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    merge_observed(nums, left, mid, right, compare, &mut ());
+}
 
-    let (mut i, mut j, mut k) = (left, mid + 1, 0);
+fn merge_observed<T, F, O>(
+    nums: &mut [T],
+    left: usize,
+    mid: usize,
+    right: usize,
+    compare: &mut F,
+    observer: &mut O,
+) where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    let mut temp = Vec::with_capacity(right - left + 1);
+    observer.alloc();
+
+    let (mut i, mut j) = (left, mid + 1);
 
     while i <= mid && j <= right {
-        if nums[i] <= nums[j] {
-            temp[k] = nums[i];
+        // take from the left run on ties to keep the sort stable
+        if stats::compare(compare, observer, &nums[i], &nums[j]) != Ordering::Greater {
+            temp.push(nums[i].clone());
             i += 1;
         } else {
-            temp[k] = nums[j];
+            temp.push(nums[j].clone());
             j += 1;
         }
-        k += 1;
     }
 
-    while i <= mid {
-        temp[k] = nums[i];
-        i += 1;
-        k += 1;
-    }
-    while j <= right {
-        temp[k] = nums[j];
-        j += 1;
-        k += 1;
-    }
+    temp.extend_from_slice(&nums[i..=mid]);
+    temp.extend_from_slice(&nums[j..=right]);
 
-    for k in 0..temp_size {
-        nums[left + k] = temp[k];
-    }
+    nums[left..=right].clone_from_slice(&temp);
+    observer.moves(temp.len(), nums);
 }
 
 #[cfg(test)]
@@ -67,4 +123,24 @@ mod tests {
         let nums = merge_sort(nums);
         assert_eq!(nums, vec![]);
     }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        merge_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        merge_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words, vec!["fig", "pear", "kiwi", "apple", "banana"]);
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        merge_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
 }
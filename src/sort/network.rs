@@ -0,0 +1,361 @@
+use std::cmp::Ordering;
+
+// Comparators of the smallest known sorting network for each length, one
+// line per layer of comparators that touch distinct positions.
+#[rustfmt::skip]
+const NETWORKS: [&[(usize, usize)]; 17] = [
+    &[],
+    &[],
+    // n = 2, 1 comparator, depth 1
+    &[
+        (0, 1),
+    ],
+    // n = 3, 3 comparators, depth 3
+    &[
+        (0, 2),
+        (0, 1),
+        (1, 2),
+    ],
+    // n = 4, 5 comparators, depth 3
+    &[
+        (0, 2), (1, 3),
+        (0, 1), (2, 3),
+        (1, 2),
+    ],
+    // n = 5, 9 comparators, depth 5
+    &[
+        (0, 3), (1, 4),
+        (0, 2), (1, 3),
+        (0, 1), (2, 4),
+        (1, 2), (3, 4),
+        (2, 3),
+    ],
+    // n = 6, 12 comparators, depth 5
+    &[
+        (0, 5), (1, 3), (2, 4),
+        (1, 2), (3, 4),
+        (0, 3), (2, 5),
+        (0, 1), (2, 3), (4, 5),
+        (1, 2), (3, 4),
+    ],
+    // n = 7, 16 comparators, depth 6
+    &[
+        (0, 6), (2, 3), (4, 5),
+        (0, 2), (1, 4), (3, 6),
+        (0, 1), (2, 5), (3, 4),
+        (1, 2), (4, 6),
+        (2, 3), (4, 5),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    // n = 8, 19 comparators, depth 6
+    &[
+        (0, 2), (1, 3), (4, 6), (5, 7),
+        (0, 4), (1, 5), (2, 6), (3, 7),
+        (0, 1), (2, 3), (4, 5), (6, 7),
+        (2, 4), (3, 5),
+        (1, 4), (3, 6),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    // n = 9, 25 comparators, depth 7
+    &[
+        (0, 3), (1, 7), (2, 5), (4, 8),
+        (0, 7), (2, 4), (3, 8), (5, 6),
+        (0, 2), (1, 3), (4, 5), (7, 8),
+        (1, 4), (3, 6), (5, 7),
+        (0, 1), (2, 4), (3, 5), (6, 8),
+        (2, 3), (4, 5), (6, 7),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    // n = 10, 29 comparators, depth 8
+    &[
+        (0, 8), (1, 9), (2, 7), (3, 5), (4, 6),
+        (0, 2), (1, 4), (5, 8), (7, 9),
+        (0, 3), (2, 4), (5, 7), (6, 9),
+        (0, 1), (3, 6), (8, 9),
+        (1, 5), (2, 3), (4, 8), (6, 7),
+        (1, 2), (3, 5), (4, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+        (3, 4), (5, 6),
+    ],
+    // n = 11, 35 comparators, depth 8
+    &[
+        (0, 9), (1, 6), (2, 4), (3, 7), (5, 8),
+        (0, 1), (3, 5), (4, 10), (6, 9), (7, 8),
+        (1, 3), (2, 5), (4, 7), (8, 10),
+        (0, 4), (1, 2), (3, 7), (5, 9), (6, 8),
+        (0, 1), (2, 6), (4, 5), (7, 8), (9, 10),
+        (2, 4), (3, 6), (5, 7), (8, 9),
+        (1, 2), (3, 4), (5, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+    ],
+    // n = 12, 39 comparators, depth 9
+    &[
+        (0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9),
+        (0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11),
+        (0, 2), (1, 6), (5, 10), (9, 11),
+        (0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10),
+        (1, 4), (3, 5), (6, 8), (7, 10),
+        (1, 3), (2, 5), (6, 9), (8, 10),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (4, 6), (5, 7),
+        (3, 4), (5, 6), (7, 8),
+    ],
+    // n = 13, 45 comparators, depth 10
+    &[
+        (0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8),
+        (1, 6), (2, 3), (4, 11), (7, 9), (8, 10),
+        (0, 4), (1, 2), (3, 6), (7, 8), (9, 10), (11, 12),
+        (4, 6), (5, 9), (8, 11), (10, 12),
+        (0, 5), (3, 8), (4, 7), (6, 11), (9, 10),
+        (0, 1), (2, 5), (6, 9), (7, 8), (10, 11),
+        (1, 3), (2, 4), (5, 6), (9, 10),
+        (1, 2), (3, 4), (5, 7), (6, 8),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (3, 4), (5, 6),
+    ],
+    // n = 14, 51 comparators, depth 10
+    &[
+        (0, 13), (1, 12), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+    // n = 15, 56 comparators, depth 10
+    &[
+        (0, 13), (1, 12), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+    // n = 16, 60 comparators, depth 10
+    &[
+        (0, 13), (1, 12), (2, 15), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (10, 15), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (14, 15),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14), (13, 15),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+];
+
+// comparator pairs used to sort `n` elements, n must be at most 16
+pub fn network(n: usize) -> &'static [(usize, usize)] {
+    NETWORKS[n]
+}
+
+pub fn network_sort<T: Ord, const N: usize>(arr: &mut [T; N]) {
+    network_sort_by(arr, T::cmp);
+}
+
+// Sort a fixed size array of at most 16 elements with a sorting network,
+// the same comparisons run whatever the input is.
+pub fn network_sort_by<T, F, const N: usize>(arr: &mut [T; N], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    const { assert!(N <= 16, "sorting networks go up to 16 elements") };
+
+    for &(i, j) in NETWORKS[N] {
+        compare_exchange(arr, i, j, true, &mut compare);
+    }
+}
+
+pub fn network_sort_by_key<T, K, F, const N: usize>(arr: &mut [T; N], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    network_sort_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
+pub fn bitonic_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    bitonic_sort_slice(&mut nums);
+    nums
+}
+
+pub fn bitonic_sort_slice<T: Ord>(nums: &mut [T]) {
+    bitonic_sort_by(nums, T::cmp);
+}
+
+// Bitonic sorting network, panics unless the length is a power of two.
+pub fn bitonic_sort_by<T, F>(nums: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = nums.len();
+    if n <= 1 {
+        return;
+    }
+    assert!(
+        n.is_power_of_two(),
+        "bitonic sort needs a power of two length, got {}",
+        n
+    );
+
+    // merge bitonic runs of length k, alternating ascending and descending
+    // runs so each pair of them forms a bitonic run of length 2k
+    let mut k = 2;
+    while k <= n {
+        let mut j = k / 2;
+        while j > 0 {
+            for i in 0..n {
+                let l = i ^ j;
+                if l > i {
+                    compare_exchange(nums, i, l, i & k == 0, &mut compare);
+                }
+            }
+            j /= 2;
+        }
+        k *= 2;
+    }
+}
+
+pub fn bitonic_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    bitonic_sort_by(nums, |a, b| f(a).cmp(&f(b)));
+}
+
+// put the smaller of nums[i] and nums[j] first, or last if not `ascending`
+fn compare_exchange<T, F>(nums: &mut [T], i: usize, j: usize, ascending: bool, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let out_of_order = if ascending {
+        Ordering::Greater
+    } else {
+        Ordering::Less
+    };
+    if compare(&nums[i], &nums[j]) == out_of_order {
+        nums.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::insert_sort::insert_sort_slice;
+
+    // By the 0/1 principle a network sorting every input of zeros and ones
+    // sorts every input.
+    fn zero_one<const N: usize>(sort: fn(&mut [u8; N])) {
+        for bits in 0..1u32 << N {
+            let mut arr = [0u8; N];
+            for (i, x) in arr.iter_mut().enumerate() {
+                *x = (bits >> i & 1) as u8;
+            }
+            let mut expected = arr;
+            insert_sort_slice(&mut expected);
+
+            sort(&mut arr);
+
+            assert_eq!(arr, expected, "input {:0width$b}", bits, width = N);
+        }
+    }
+
+    #[test]
+    fn init() {
+        let mut arr = [6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        network_sort(&mut arr);
+
+        assert_eq!(arr, [1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let mut arr: [i32; 0] = [];
+        network_sort(&mut arr);
+        let mut arr = [1];
+        network_sort(&mut arr);
+        assert_eq!(arr, [1]);
+    }
+
+    #[test]
+    fn by() {
+        let mut arr = [6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        network_sort_by(&mut arr, |a, b| b.cmp(a));
+        assert_eq!(arr, [9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = ["pear", "fig", "banana", "kiwi", "apple"];
+        network_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "apple");
+        assert_eq!(words[4], "banana");
+
+        // owned values are moved, never duplicated or dropped
+        let mut words = ["pear", "fig", "banana", "kiwi"].map(String::from);
+        network_sort(&mut words);
+        assert_eq!(words, ["banana", "fig", "kiwi", "pear"]);
+    }
+
+    #[test]
+    fn networks() {
+        let sizes: Vec<usize> = (2..=16).map(|n| network(n).len()).collect();
+        assert_eq!(
+            sizes,
+            vec![1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60]
+        );
+
+        zero_one::<2>(network_sort);
+        zero_one::<3>(network_sort);
+        zero_one::<4>(network_sort);
+        zero_one::<5>(network_sort);
+        zero_one::<6>(network_sort);
+        zero_one::<7>(network_sort);
+        zero_one::<8>(network_sort);
+        zero_one::<9>(network_sort);
+        zero_one::<10>(network_sort);
+        zero_one::<11>(network_sort);
+        zero_one::<12>(network_sort);
+        zero_one::<13>(network_sort);
+        zero_one::<14>(network_sort);
+        zero_one::<15>(network_sort);
+        zero_one::<16>(network_sort);
+    }
+
+    #[test]
+    fn bitonic() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5, 0, 8, 6, 1];
+        let nums = bitonic_sort(nums);
+        assert_eq!(nums, vec![0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 6, 6, 7, 8, 8, 9]);
+        assert_eq!(bitonic_sort(vec![]), vec![]);
+
+        let mut words = ["pear", "fig", "banana", "kiwi"];
+        bitonic_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "banana");
+
+        zero_one::<2>(|arr| bitonic_sort_slice(arr));
+        zero_one::<4>(|arr| bitonic_sort_slice(arr));
+        zero_one::<8>(|arr| bitonic_sort_slice(arr));
+        zero_one::<16>(|arr| bitonic_sort_slice(arr));
+
+        let mut nums: Vec<i32> = (0..1024).map(|i| (i * 7919) % 1009).collect();
+        let mut expected = nums.clone();
+        expected.sort();
+        bitonic_sort_by(&mut nums, |a, b| a.cmp(b));
+        assert_eq!(nums, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bitonic_not_power_of_two() {
+        bitonic_sort(vec![3, 1, 2]);
+    }
+}
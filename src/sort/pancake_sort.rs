@@ -0,0 +1,81 @@
+use std::cmp::Ordering;
+
+pub fn pancake_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    pancake_sort_slice(&mut nums);
+    nums
+}
+
+pub fn pancake_sort_slice<T: Ord>(nums: &mut [T]) {
+    pancake_sort_by(nums, T::cmp);
+}
+
+// only ever reverses a prefix of the slice
+pub fn pancake_sort_by<T, F>(nums: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    // [0, size) will be process
+    for size in (2..=nums.len()).rev() {
+        let mut max = 0;
+        for i in 1..size {
+            if compare(&nums[i], &nums[max]) != Ordering::Less {
+                max = i;
+            }
+        }
+        if max == size - 1 {
+            continue;
+        }
+
+        // flip the max to the top, then down to the bottom
+        nums[..=max].reverse();
+        nums[..size].reverse();
+    }
+}
+
+pub fn pancake_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    pancake_sort_by(nums, |a, b| f(a).cmp(&f(b)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        let nums = pancake_sort(nums);
+
+        assert_eq!(nums, vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let nums = vec![];
+        let nums = pancake_sort(nums);
+        assert_eq!(nums, vec![]);
+    }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        pancake_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        pancake_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "apple");
+        assert_eq!(words[4], "banana");
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        pancake_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
+}
@@ -0,0 +1,197 @@
+use std::{cmp::Ordering, thread};
+
+use super::{merge_sort, quick_sort};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelOptions {
+    // maximum number of threads sorting at the same time
+    pub threads: usize,
+    // slices shorter than this are sorted on the current thread
+    pub threshold: usize,
+}
+
+impl Default for ParallelOptions {
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            threshold: 1 << 12,
+        }
+    }
+}
+
+pub fn merge_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    merge_sort_slice(&mut nums);
+    nums
+}
+
+pub fn merge_sort_slice<T: Ord + Clone + Send>(nums: &mut [T]) {
+    merge_sort_with(nums, T::cmp, ParallelOptions::default());
+}
+
+pub fn merge_sort_by<T, F>(nums: &mut [T], compare: F)
+where
+    T: Clone + Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    merge_sort_with(nums, compare, ParallelOptions::default());
+}
+
+// Same output as the sequential `merge_sort_by`, stable as well.
+pub fn merge_sort_with<T, F>(nums: &mut [T], compare: F, options: ParallelOptions)
+where
+    T: Clone + Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    _merge_sort(nums, &compare, options.threads.max(1), options.threshold);
+}
+
+fn _merge_sort<T, F>(nums: &mut [T], compare: &F, threads: usize, threshold: usize)
+where
+    T: Clone + Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let n = nums.len();
+    if threads < 2 || n <= threshold.max(1) {
+        merge_sort::merge_sort_by(nums, compare);
+        return;
+    }
+
+    // split like the sequential version, left half is [0, mid]
+    let mid = (n - 1) / 2;
+    let (left, right) = nums.split_at_mut(mid + 1);
+    let left_threads = threads / 2;
+    thread::scope(|s| {
+        s.spawn(|| _merge_sort(left, compare, left_threads, threshold));
+        _merge_sort(right, compare, threads - left_threads, threshold);
+    });
+
+    let mut compare = compare;
+    merge_sort::merge(nums, 0, mid, n - 1, &mut compare);
+}
+
+pub fn quick_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    quick_sort_slice(&mut nums);
+    nums
+}
+
+pub fn quick_sort_slice<T: Ord + Send>(nums: &mut [T]) {
+    quick_sort_with(nums, T::cmp, ParallelOptions::default());
+}
+
+pub fn quick_sort_by<T, F>(nums: &mut [T], compare: F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    quick_sort_with(nums, compare, ParallelOptions::default());
+}
+
+// Partitions exactly like the sequential `quick_sort_by`, so the output is
+// the same even for elements that compare equal.
+pub fn quick_sort_with<T, F>(nums: &mut [T], compare: F, options: ParallelOptions)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    _quick_sort(nums, &compare, options.threads.max(1), options.threshold);
+}
+
+fn _quick_sort<T, F>(nums: &mut [T], compare: &F, threads: usize, threshold: usize)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let mut compare_mut = compare;
+    if threads < 2 || nums.len() <= threshold.max(1) {
+        quick_sort::_quick_sort_by(nums, &mut compare_mut);
+        return;
+    }
+
+    let pivot = quick_sort::partition(nums, 0, nums.len() - 1, &mut compare_mut);
+
+    let (left, right) = nums.split_at_mut(pivot);
+    let right = &mut right[1..];
+    let left_threads = threads / 2;
+    thread::scope(|s| {
+        s.spawn(|| _quick_sort(left, compare, left_threads, threshold));
+        _quick_sort(right, compare, threads - left_threads, threshold);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> ParallelOptions {
+        ParallelOptions {
+            threads: 4,
+            threshold: 16,
+        }
+    }
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        assert_eq!(
+            merge_sort(nums.clone()),
+            vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]
+        );
+        assert_eq!(quick_sort(nums), vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        assert_eq!(merge_sort(vec![]), vec![]);
+        assert_eq!(quick_sort(vec![]), vec![]);
+    }
+
+    #[test]
+    fn merge_same_as_sequential() {
+        // (key, original index) pairs, only the key is compared
+        let pairs: Vec<(i32, usize)> = (0..5000)
+            .map(|i| ((i * 7919 % 1009) as i32 / 8, i))
+            .collect();
+
+        let mut expected = pairs.clone();
+        merge_sort::merge_sort_by_key(&mut expected, |p| p.0);
+
+        let mut nums = pairs.clone();
+        merge_sort_with(&mut nums, |a, b| a.0.cmp(&b.0), options());
+        assert_eq!(nums, expected);
+
+        let mut nums = pairs;
+        merge_sort_by(&mut nums, |a, b| a.0.cmp(&b.0));
+        assert_eq!(nums, expected);
+    }
+
+    #[test]
+    fn quick_same_as_sequential() {
+        let pairs: Vec<(i32, usize)> = (0..5000)
+            .map(|i| ((i * 7919 % 1009) as i32 / 8, i))
+            .collect();
+
+        let mut expected = pairs.clone();
+        quick_sort::quick_sort_by_key(&mut expected, |p| p.0);
+
+        let mut nums = pairs.clone();
+        quick_sort_with(&mut nums, |a, b| a.0.cmp(&b.0), options());
+        assert_eq!(nums, expected);
+
+        let mut nums = pairs;
+        quick_sort_by(&mut nums, |a, b| a.0.cmp(&b.0));
+        assert_eq!(nums, expected);
+    }
+
+    #[test]
+    fn single_thread() {
+        let mut nums: Vec<i32> = (0..1000).rev().collect();
+        let options = ParallelOptions {
+            threads: 1,
+            threshold: 0,
+        };
+        merge_sort_with(&mut nums, i32::cmp, options);
+        assert_eq!(nums, (0..1000).collect::<Vec<_>>());
+
+        let mut nums: Vec<i32> = (0..1000).rev().collect();
+        quick_sort_with(&mut nums, i32::cmp, options);
+        assert_eq!(nums, (0..1000).collect::<Vec<_>>());
+    }
+}
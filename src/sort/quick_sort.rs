@@ -1,47 +1,382 @@
+use std::cmp::Ordering;
+
+use super::stats::{self, SortObserver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PartitionScheme {
+    // Hoare style, one pivot, equal keys end up on both sides
+    #[default]
+    TwoWay,
+    // Dijkstra's fat partition, keys equal to the pivot are done in one pass
+    ThreeWay,
+    // Yaroslavskiy's two pivots, three parts per pass
+    DualPivot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QuickSortOptions {
+    pub partition: PartitionScheme,
+}
+
 pub fn quick_sort(mut nums: Vec<i32>) -> Vec<i32> {
-    let right = nums.len() as i32 - 1;
-    _quick_sort(&mut nums, 0, right);
+    quick_sort_slice(&mut nums);
     nums
 }
 
-pub fn _quick_sort(nums: &mut [i32], mut left: i32, mut right: i32) {
-    while left < right {
-        let pivot = partition(nums, left as usize, right as usize) as i32;
+pub fn quick_sort_slice<T: Ord>(nums: &mut [T]) {
+    _quick_sort_by(nums, &mut T::cmp);
+}
+
+pub fn _quick_sort(nums: &mut [i32], left: i32, right: i32) {
+    if left < right {
+        _quick_sort_by(&mut nums[left as usize..=right as usize], &mut i32::cmp);
+    }
+}
+
+pub fn quick_sort_by<T, F>(nums: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    _quick_sort_by(nums, &mut compare);
+}
+
+pub fn quick_sort_with<T, F>(nums: &mut [T], compare: F, options: QuickSortOptions)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    quick_sort_with_observed(nums, compare, options, &mut ());
+}
 
-        if pivot - left < right - pivot {
-            _quick_sort(nums, left, pivot - 1);
+pub fn quick_sort_observed<T, F, O>(nums: &mut [T], compare: F, observer: &mut O)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    quick_sort_with_observed(nums, compare, QuickSortOptions::default(), observer);
+}
+
+pub fn quick_sort_with_observed<T, F, O>(
+    nums: &mut [T],
+    mut compare: F,
+    options: QuickSortOptions,
+    observer: &mut O,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    let n = nums.len();
+    match options.partition {
+        PartitionScheme::TwoWay => _quick_sort_observed(nums, 0, n, &mut compare, observer),
+        PartitionScheme::ThreeWay => _three_way_observed(nums, 0, n, &mut compare, observer),
+        PartitionScheme::DualPivot => _dual_pivot_observed(nums, 0, n, &mut compare, observer),
+    }
+}
+
+pub fn quick_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    _quick_sort_by(nums, &mut |a: &T, b: &T| f(a).cmp(&f(b)));
+}
+
+pub(crate) fn _quick_sort_by<T, F>(nums: &mut [T], compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    _quick_sort_observed(nums, 0, nums.len(), compare, &mut ());
+}
+
+// sorts nums[left..right], indices stay relative to the whole slice so the
+// observer always sees all of it
+fn _quick_sort_observed<T, F, O>(
+    nums: &mut [T],
+    mut left: usize,
+    mut right: usize,
+    compare: &mut F,
+    observer: &mut O,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    while right - left > 1 {
+        let pivot = partition_observed(nums, left, right - 1, compare, observer);
+
+        // recurse into the shorter side, loop on the longer one
+        observer.enter();
+        if pivot - left < right - pivot - 1 {
+            _quick_sort_observed(nums, left, pivot, compare, observer);
             left = pivot + 1;
         } else {
-            _quick_sort(nums, pivot + 1, right);
-            right = pivot - 1;
+            _quick_sort_observed(nums, pivot + 1, right, compare, observer);
+            right = pivot;
         }
+        observer.leave();
     }
 }
 
-fn partition(nums: &mut [i32], left: usize, right: usize) -> usize {
-    let median = median_three(nums, left, left + (right - left) / 2, right);
-    (nums[left], nums[median]) = (nums[median], nums[left]);
+fn _three_way_observed<T, F, O>(
+    nums: &mut [T],
+    mut left: usize,
+    mut right: usize,
+    compare: &mut F,
+    observer: &mut O,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    while right - left > 1 {
+        let (lt, gt) = partition_three_way_observed(nums, left, right - 1, compare, observer);
 
-    let pivot = nums[left];
+        observer.enter();
+        if lt - left < right - gt {
+            _three_way_observed(nums, left, lt, compare, observer);
+            left = gt;
+        } else {
+            _three_way_observed(nums, gt, right, compare, observer);
+            right = lt;
+        }
+        observer.leave();
+    }
+}
+
+// Partition nums[left..=right] into less, equal and greater than the pivot,
+// returns the bounds of the equal part as nums[lt..gt].
+fn partition_three_way_observed<T, F, O>(
+    nums: &mut [T],
+    left: usize,
+    right: usize,
+    compare: &mut F,
+    observer: &mut O,
+) -> (usize, usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    let median = {
+        let mut compare = |a: &T, b: &T| stats::compare(compare, observer, a, b);
+        median_three(nums, left, left + (right - left) / 2, right, &mut compare)
+    };
+    stats::swap(nums, left, median, observer);
+
+    // nums[lt..i] all equal the pivot, so nums[lt] always holds one
+    let (mut lt, mut i, mut gt) = (left, left + 1, right + 1);
+    while i < gt {
+        match stats::compare(compare, observer, &nums[i], &nums[lt]) {
+            Ordering::Less => {
+                stats::swap(nums, lt, i, observer);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                stats::swap(nums, i, gt, observer);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    (lt, gt)
+}
 
+fn _dual_pivot_observed<T, F, O>(
+    nums: &mut [T],
+    mut left: usize,
+    mut right: usize,
+    compare: &mut F,
+    observer: &mut O,
+) where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    while right - left > 1 {
+        let (p, q, middle) =
+            partition_dual_pivot_observed(nums, left, right - 1, compare, observer);
+
+        // recurse into the two shorter parts, loop on the longest one
+        let mut parts = [(left, p), middle, (q + 1, right)];
+        parts.sort_unstable_by_key(|&(l, r)| r - l);
+        observer.enter();
+        for &(l, r) in &parts[..2] {
+            _dual_pivot_observed(nums, l, r, compare, observer);
+        }
+        observer.leave();
+        (left, right) = parts[2];
+    }
+}
+
+// Partition nums[left..=right] around two pivots p <= q, returns where they
+// end up and the part of the middle still unsorted, elements equal to either
+// pivot are moved out of it.
+fn partition_dual_pivot_observed<T, F, O>(
+    nums: &mut [T],
+    left: usize,
+    right: usize,
+    compare: &mut F,
+    observer: &mut O,
+) -> (usize, usize, (usize, usize))
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    // the tertiles make better pivots than the ends on sorted input
+    let third = (right - left + 1) / 3;
+    if third > 0 {
+        stats::swap(nums, left, left + third, observer);
+        stats::swap(nums, right, right - third, observer);
+    }
+    if stats::compare(compare, observer, &nums[left], &nums[right]) == Ordering::Greater {
+        stats::swap(nums, left, right, observer);
+    }
+
+    // pivots stay at nums[left] and nums[right] until the final swaps
+    // [left + 1, l) < p, [l, k) between, (g, right - 1] > q
+    let (mut l, mut k, mut g) = (left + 1, left + 1, right - 1);
+    while k <= g {
+        if stats::compare(compare, observer, &nums[k], &nums[left]) == Ordering::Less {
+            stats::swap(nums, k, l, observer);
+            l += 1;
+        } else if stats::compare(compare, observer, &nums[k], &nums[right]) == Ordering::Greater {
+            while k < g
+                && stats::compare(compare, observer, &nums[g], &nums[right]) == Ordering::Greater
+            {
+                g -= 1;
+            }
+            stats::swap(nums, k, g, observer);
+            g -= 1;
+            if stats::compare(compare, observer, &nums[k], &nums[left]) == Ordering::Less {
+                stats::swap(nums, k, l, observer);
+                l += 1;
+            }
+        }
+        k += 1;
+    }
+    let (p, q) = (l - 1, g + 1);
+    stats::swap(nums, left, p, observer);
+    stats::swap(nums, right, q, observer);
+
+    // equal pivots, the whole middle equals them
+    if stats::compare(compare, observer, &nums[p], &nums[q]) == Ordering::Equal {
+        return (p, q, (p + 1, p + 1));
+    }
+
+    // move keys equal to the pivots to the ends of the middle, so runs of
+    // duplicates are not partitioned again
+    // [p + 1, less) == p, [less, k) between, [great, q) == q
+    let (mut less, mut great) = (p + 1, q);
+    while less < great
+        && stats::compare(compare, observer, &nums[less], &nums[p]) == Ordering::Equal
+    {
+        less += 1;
+    }
+    while less < great
+        && stats::compare(compare, observer, &nums[great - 1], &nums[q]) == Ordering::Equal
+    {
+        great -= 1;
+    }
+    let mut k = less;
+    while k < great {
+        if stats::compare(compare, observer, &nums[k], &nums[p]) == Ordering::Equal {
+            stats::swap(nums, k, less, observer);
+            less += 1;
+        } else if stats::compare(compare, observer, &nums[k], &nums[q]) == Ordering::Equal {
+            while k < great - 1
+                && stats::compare(compare, observer, &nums[great - 1], &nums[q]) == Ordering::Equal
+            {
+                great -= 1;
+            }
+            great -= 1;
+            stats::swap(nums, k, great, observer);
+            if stats::compare(compare, observer, &nums[k], &nums[p]) == Ordering::Equal {
+                stats::swap(nums, k, less, observer);
+                less += 1;
+            }
+        }
+        k += 1;
+    }
+    (p, q, (less, great))
+}
+
+pub(crate) fn partition<T, F>(nums: &mut [T], left: usize, right: usize, compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    partition_observed(nums, left, right, compare, &mut ())
+}
+
+fn partition_observed<T, F, O>(
+    nums: &mut [T],
+    left: usize,
+    right: usize,
+    compare: &mut F,
+    observer: &mut O,
+) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    let median = {
+        let mut compare = |a: &T, b: &T| stats::compare(compare, observer, a, b);
+        median_three(nums, left, left + (right - left) / 2, right, &mut compare)
+    };
+    stats::swap(nums, left, median, observer);
+
+    partition_around_observed(nums, left, right, compare, observer)
+}
+
+// partition nums[left..=right] around the pivot already at nums[left]
+pub(crate) fn partition_around<T, F>(
+    nums: &mut [T],
+    left: usize,
+    right: usize,
+    compare: &mut F,
+) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    partition_around_observed(nums, left, right, compare, &mut ())
+}
+
+fn partition_around_observed<T, F, O>(
+    nums: &mut [T],
+    left: usize,
+    right: usize,
+    compare: &mut F,
+    observer: &mut O,
+) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    // pivot stays at nums[left] until the final swap
     let (mut l, mut r) = (left, right);
     while l < r {
-        while l < r && nums[r] >= pivot {
+        while l < r && stats::compare(compare, observer, &nums[r], &nums[left]) != Ordering::Less {
             r -= 1;
         }
-        while l < r && nums[l] <= pivot {
+        while l < r && stats::compare(compare, observer, &nums[l], &nums[left]) != Ordering::Greater
+        {
             l += 1;
         }
-        (nums[l], nums[r]) = (nums[r], nums[l]);
+        stats::swap(nums, l, r, observer);
     }
-    (nums[l], nums[left]) = (nums[left], nums[l]);
+    stats::swap(nums, l, left, observer);
     l
 }
 
-fn median_three(nums: &mut [i32], left: usize, mid: usize, right: usize) -> usize {
-    if (nums[left] < nums[mid]) ^ (nums[left] < nums[right]) {
+pub(crate) fn median_three<T, F>(
+    nums: &[T],
+    left: usize,
+    mid: usize,
+    right: usize,
+    compare: &mut F,
+) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut less = |a: usize, b: usize| compare(&nums[a], &nums[b]) == Ordering::Less;
+    if less(left, mid) ^ less(left, right) {
         left
-    } else if (nums[mid] < nums[left]) ^ (nums[mid] < nums[right]) {
+    } else if less(mid, left) ^ less(mid, right) {
         mid
     } else {
         right
@@ -51,6 +386,7 @@ fn median_three(nums: &mut [i32], left: usize, mid: usize, right: usize) -> usiz
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sort::stats::SortStats;
 
     #[test]
     fn init() {
@@ -64,4 +400,86 @@ mod tests {
         let nums = quick_sort(nums);
         assert_eq!(nums, vec![]);
     }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        quick_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        quick_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "apple");
+        assert_eq!(words[4], "banana");
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        quick_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        _quick_sort(&mut nums, 3, 8);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
+
+    const SCHEMES: [PartitionScheme; 3] = [
+        PartitionScheme::TwoWay,
+        PartitionScheme::ThreeWay,
+        PartitionScheme::DualPivot,
+    ];
+
+    #[test]
+    fn schemes() {
+        for partition in SCHEMES {
+            let options = QuickSortOptions { partition };
+
+            let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+            quick_sort_with(&mut nums, i32::cmp, options);
+            assert_eq!(nums, vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+            quick_sort_with(&mut nums, |a, b| b.cmp(a), options);
+            assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+            for modulo in [2, 3, 17, 1009] {
+                let mut nums: Vec<i32> = (0..3000).map(|i| (i * 7919) % modulo).collect();
+                let mut expected = nums.clone();
+                expected.sort();
+                quick_sort_with(&mut nums, i32::cmp, options);
+                assert_eq!(nums, expected);
+            }
+
+            let mut nums: Vec<i32> = (0..1000).collect();
+            quick_sort_with(&mut nums, |a, b| b.cmp(a), options);
+            assert_eq!(nums, (0..1000).rev().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn all_equal() {
+        let n = 2000;
+        let comparisons = |partition| {
+            let mut nums = vec![7; n];
+            let mut stats = SortStats::new();
+            quick_sort_with_observed(
+                &mut nums,
+                i32::cmp,
+                QuickSortOptions { partition },
+                &mut stats,
+            );
+            stats.comparisons
+        };
+
+        // a single pass puts every key in place
+        assert!(comparisons(PartitionScheme::ThreeWay) <= 2 * n);
+        assert!(comparisons(PartitionScheme::DualPivot) <= 3 * n);
+        // while two way partition peels off one element at a time
+        assert!(comparisons(PartitionScheme::TwoWay) >= n * n / 4);
+    }
 }
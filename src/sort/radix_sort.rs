@@ -0,0 +1,214 @@
+use super::{counting_sort::counting_pass, insert_sort::insert_sort_by};
+
+// buckets at most this long are finished by insertion sort in msd passes
+const INSERT_THRES: usize = 16;
+
+// Integer types sorted byte by byte, least significant first.
+pub trait RadixKey: Copy {
+    const BYTES: usize;
+
+    // unsigned key with the same order as `self`
+    fn radix_key(&self) -> u64;
+}
+
+impl RadixKey for u32 {
+    const BYTES: usize = 4;
+
+    fn radix_key(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl RadixKey for u64 {
+    const BYTES: usize = 8;
+
+    fn radix_key(&self) -> u64 {
+        *self
+    }
+}
+
+impl RadixKey for i32 {
+    const BYTES: usize = 4;
+
+    // flipping the sign bit moves negatives below positives
+    fn radix_key(&self) -> u64 {
+        (*self as u32 ^ (1 << 31)) as u64
+    }
+}
+
+impl RadixKey for i64 {
+    const BYTES: usize = 8;
+
+    fn radix_key(&self) -> u64 {
+        *self as u64 ^ (1 << 63)
+    }
+}
+
+pub fn radix_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    radix_sort_slice(&mut nums);
+    nums
+}
+
+// lsd radix sort
+pub fn radix_sort_slice<T: RadixKey>(nums: &mut [T]) {
+    let mut buf = nums.to_vec();
+    for byte in 0..T::BYTES {
+        let shift = byte * 8;
+        counting_pass(nums, &mut buf, 256, |num| {
+            (num.radix_key() >> shift) as usize & 0xff
+        });
+        nums.copy_from_slice(&buf);
+    }
+}
+
+// msd radix sort for byte strings, e.g. `&[u8]`, `&str` or `String`
+pub fn radix_sort_bytes<S: AsRef<[u8]>>(strs: &mut [S]) {
+    let n = strs.len();
+    // sort indices so the strings themselves only move once, at the end
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut buf = vec![0; n];
+    let bytes = |i: usize| strs[i].as_ref();
+
+    // ranges of `order` left to sort, every string in one shares its first
+    // `depth` bytes; a work list instead of recursion keeps long common
+    // prefixes off the call stack
+    let mut pending = vec![(0, n, 0)];
+    while let Some((lo, hi, mut depth)) = pending.pop() {
+        let range = &mut order[lo..hi];
+        if range.len() <= INSERT_THRES {
+            insert_sort_by(range, |&a, &b| bytes(a)[depth..].cmp(&bytes(b)[depth..]));
+            continue;
+        }
+
+        // step over bytes the whole range shares without a bucket pass
+        loop {
+            let first = bytes(range[0]).get(depth);
+            if first.is_none() || !range[1..].iter().all(|&i| bytes(i).get(depth) == first) {
+                break;
+            }
+            depth += 1;
+        }
+
+        // bucket 0 holds the strings that end at `depth`
+        let buf = &mut buf[lo..hi];
+        let starts = counting_pass(range, buf, 257, |&i| {
+            bytes(i).get(depth).map_or(0, |&b| b as usize + 1)
+        });
+        range.copy_from_slice(buf);
+
+        for b in 1..257 {
+            let end = if b == 256 { range.len() } else { starts[b + 1] };
+            if end - starts[b] > 1 {
+                pending.push((lo + starts[b], lo + end, depth + 1));
+            }
+        }
+    }
+
+    apply_order(strs, &mut order);
+}
+
+// Move `strs[order[i]]` to position i, following the permutation's cycles
+// with swaps. Leaves `order` as the identity.
+fn apply_order<S>(strs: &mut [S], order: &mut [usize]) {
+    for i in 0..order.len() {
+        let mut cur = i;
+        while order[cur] != i {
+            let next = order[cur];
+            strs.swap(cur, next);
+            order[cur] = cur;
+            cur = next;
+        }
+        order[cur] = cur;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        let nums = radix_sort(nums);
+
+        assert_eq!(nums, vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let nums = vec![];
+        let nums = radix_sort(nums);
+        assert_eq!(nums, vec![]);
+    }
+
+    #[test]
+    fn signed() {
+        let nums = vec![6, -4, 2, i32::MIN, 0, -1, i32::MAX, 3, -70000, 2];
+
+        let nums = radix_sort(nums);
+
+        assert_eq!(
+            nums,
+            vec![i32::MIN, -70000, -4, -1, 0, 2, 2, 3, 6, i32::MAX]
+        );
+
+        let mut nums: Vec<i64> = vec![1 << 40, -(1 << 40), i64::MAX, i64::MIN, 0, -3];
+        radix_sort_slice(&mut nums);
+        assert_eq!(nums, vec![i64::MIN, -(1 << 40), -3, 0, 1 << 40, i64::MAX]);
+    }
+
+    #[test]
+    fn unsigned() {
+        let mut nums: Vec<u32> = vec![u32::MAX, 0, 256, 255, 65536, 1];
+        radix_sort_slice(&mut nums);
+        assert_eq!(nums, vec![0, 1, 255, 256, 65536, u32::MAX]);
+
+        let mut nums: Vec<u64> = (0..1000).map(|i| (i * 2654435761) % 100003).collect();
+        let mut expected = nums.clone();
+        expected.sort();
+        radix_sort_slice(&mut nums);
+        assert_eq!(nums, expected);
+    }
+
+    #[test]
+    fn bytes() {
+        let mut words = vec!["pear", "fig", "banana", "", "kiwi", "apple", "app", "figs"];
+        radix_sort_bytes(&mut words);
+        assert_eq!(
+            words,
+            vec!["", "app", "apple", "banana", "fig", "figs", "kiwi", "pear"]
+        );
+
+        // enough strings to go through the msd buckets
+        let mut words: Vec<String> = (0..500).map(|i| format!("{}", i * 7919 % 1000)).collect();
+        let mut expected = words.clone();
+        expected.sort();
+        radix_sort_bytes(&mut words);
+        assert_eq!(words, expected);
+
+        let mut raw: Vec<&[u8]> = vec![b"\xff", b"\x00\x01", b"\x00", b""];
+        radix_sort_bytes(&mut raw);
+        assert_eq!(raw, vec![b"" as &[u8], b"\x00", b"\x00\x01", b"\xff"]);
+    }
+
+    #[test]
+    fn long_prefix() {
+        // a prefix far deeper than the call stack could recurse
+        let prefix = "x".repeat(100_000);
+        let mut words: Vec<String> = (0..100)
+            .map(|i| format!("{}{}", prefix, i * 37 % 100))
+            .chain(["".to_string(), prefix.clone()])
+            .collect();
+        let mut expected = words.clone();
+        expected.sort();
+        radix_sort_bytes(&mut words);
+        assert_eq!(words, expected);
+    }
+
+    #[test]
+    fn order() {
+        let mut strs = vec!['a', 'b', 'c', 'd', 'e'];
+        let mut order = vec![3, 0, 4, 1, 2];
+        apply_order(&mut strs, &mut order);
+        assert_eq!(strs, vec!['d', 'a', 'e', 'b', 'c']);
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,227 @@
+use std::cmp::Ordering;
+
+use super::{
+    insert_sort::insert_sort_by,
+    intro_sort::intro_sort_by,
+    quick_sort::{partition, partition_around},
+};
+
+// ranges at most this long are finished by insertion sort
+const INSERT_THRES: usize = 10;
+
+// Reorder `nums` so that `nums[k]` is the element that would be there if it
+// were sorted, everything before it not greater and everything after it not
+// less. Returns (before, nums[k], after). Panics if `k >= nums.len()`.
+pub fn select_nth_unstable<T: Ord>(nums: &mut [T], k: usize) -> (&mut [T], &mut T, &mut [T]) {
+    select_nth_unstable_by(nums, k, T::cmp)
+}
+
+pub fn select_nth_unstable_by<T, F>(
+    nums: &mut [T],
+    k: usize,
+    mut compare: F,
+) -> (&mut [T], &mut T, &mut [T])
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = nums.len();
+    assert!(k < n, "select index {} out of range for length {}", k, n);
+
+    // quickselect gets as many rounds as intro sort gets levels
+    let rounds = 2 * n.ilog2() as usize;
+    _select(nums, k, rounds, &mut compare);
+
+    let (before, rest) = nums.split_at_mut(k);
+    let (nth, after) = rest.split_first_mut().unwrap();
+    (before, nth, after)
+}
+
+pub fn select_nth_unstable_by_key<T, K, F>(
+    nums: &mut [T],
+    k: usize,
+    mut f: F,
+) -> (&mut [T], &mut T, &mut [T])
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    select_nth_unstable_by(nums, k, |a, b| f(a).cmp(&f(b)))
+}
+
+// Sort the `k` smallest elements into nums[..k], the order of the rest is
+// unspecified.
+pub fn partial_sort<T: Ord>(nums: &mut [T], k: usize) {
+    partial_sort_by(nums, k, T::cmp);
+}
+
+pub fn partial_sort_by<T, F>(nums: &mut [T], k: usize, mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let k = k.min(nums.len());
+    if k == 0 {
+        return;
+    }
+    if k < nums.len() {
+        select_nth_unstable_by(nums, k - 1, &mut compare);
+    }
+    intro_sort_by(&mut nums[..k], compare);
+}
+
+// Quickselect, falling back to median of medians pivots once `rounds` runs
+// out so the worst case stays linear.
+fn _select<T, F>(mut nums: &mut [T], mut k: usize, mut rounds: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    loop {
+        let n = nums.len();
+        if n <= INSERT_THRES {
+            insert_sort_by(nums, &mut *compare);
+            return;
+        }
+
+        let pivot = if rounds > 0 {
+            rounds -= 1;
+            partition(nums, 0, n - 1, compare)
+        } else {
+            let median = median_of_medians(nums, compare);
+            nums.swap(0, median);
+            partition_around(nums, 0, n - 1, compare)
+        };
+
+        // nothing is less than the pivot, gather its duplicates so runs of
+        // equal elements are skipped in one step
+        let mut equal = pivot + 1;
+        if pivot == 0 {
+            for i in 1..n {
+                if compare(&nums[i], &nums[0]) == Ordering::Equal {
+                    nums.swap(i, equal);
+                    equal += 1;
+                }
+            }
+        }
+
+        if k < pivot {
+            nums = &mut std::mem::take(&mut nums)[..pivot];
+        } else if k < equal {
+            return;
+        } else {
+            nums = &mut std::mem::take(&mut nums)[equal..];
+            k -= equal;
+        }
+    }
+}
+
+// Index of a pivot guaranteed to have at least 3/10 of `nums` on each side.
+fn median_of_medians<T, F>(nums: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = nums.len();
+    let groups = n.div_ceil(5);
+
+    // move the median of every group of five to the front
+    for g in 0..groups {
+        let group = &mut nums[g * 5..n.min(g * 5 + 5)];
+        insert_sort_by(group, &mut *compare);
+        let median = g * 5 + group.len() / 2;
+        nums.swap(g, median);
+    }
+
+    let mid = groups / 2;
+    _select(&mut nums[..groups], mid, 0, compare);
+    mid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        let sorted = vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9];
+
+        for (k, expected) in sorted.iter().enumerate() {
+            let mut nums = nums.clone();
+            let (before, nth, after) = select_nth_unstable(&mut nums, k);
+            assert_eq!(nth, expected);
+            assert!(before.iter().all(|x| x <= expected));
+            assert!(after.iter().all(|x| x >= expected));
+        }
+    }
+
+    #[test]
+    fn large() {
+        let nums: Vec<i32> = (0..1000).map(|i| (i * 7919) % 1009 / 3).collect();
+        let mut sorted = nums.clone();
+        sorted.sort();
+
+        for k in [0, 1, 17, 499, 500, 998, 999] {
+            let mut quick = nums.clone();
+            select_nth_unstable(&mut quick, k);
+            assert_eq!(quick[k], sorted[k]);
+
+            // median of medians from the first round
+            let mut linear = nums.clone();
+            _select(&mut linear, k, 0, &mut i32::cmp);
+            assert_eq!(linear[k], sorted[k]);
+            assert!(linear[..k].iter().all(|x| x <= &sorted[k]));
+            assert!(linear[k + 1..].iter().all(|x| x >= &sorted[k]));
+        }
+    }
+
+    #[test]
+    fn all_equal() {
+        let mut nums = vec![7; 10000];
+        let (before, nth, after) = select_nth_unstable(&mut nums, 5000);
+        assert_eq!(*nth, 7);
+        assert_eq!((before.len(), after.len()), (5000, 4999));
+
+        let mut nums = vec![7; 10000];
+        _select(&mut nums, 9999, 0, &mut i32::cmp);
+        assert_eq!(nums[9999], 7);
+    }
+
+    #[test]
+    fn by_key() {
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        let (_, nth, _) = select_nth_unstable_by_key(&mut words, 4, |w| w.len());
+        assert_eq!(*nth, "banana");
+
+        let (_, nth, _) = select_nth_unstable_by(&mut words, 0, |a, b| b.cmp(a));
+        assert_eq!(*nth, "pear");
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        select_nth_unstable(&mut [1, 2, 3], 3);
+    }
+
+    #[test]
+    fn partial() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        partial_sort(&mut nums, 4);
+
+        assert_eq!(nums[..4], [1, 2, 2, 3]);
+        let mut rest = nums[4..].to_vec();
+        rest.sort();
+        assert_eq!(rest, vec![3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let mut nums: Vec<i32> = (0..100).rev().collect();
+        partial_sort_by(&mut nums, 3, |a, b| b.cmp(a));
+        assert_eq!(nums[..3], [99, 98, 97]);
+
+        let mut nums = vec![3, 1, 2];
+        partial_sort(&mut nums, 10);
+        assert_eq!(nums, vec![1, 2, 3]);
+        partial_sort(&mut nums, 0);
+        assert_eq!(nums, vec![1, 2, 3]);
+
+        let mut nums: Vec<i32> = vec![];
+        partial_sort(&mut nums, 1);
+    }
+}
@@ -1,15 +1,46 @@
+use std::cmp::Ordering;
+
+use super::stats::{self, SortObserver};
+
 pub fn select_sort(mut nums: Vec<i32>) -> Vec<i32> {
-    let n = nums.len() as i32;
-    for i in 0..n - 1 {
+    select_sort_slice(&mut nums);
+    nums
+}
+
+pub fn select_sort_slice<T: Ord>(nums: &mut [T]) {
+    select_sort_by(nums, T::cmp);
+}
+
+pub fn select_sort_by<T, F>(nums: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    select_sort_observed(nums, compare, &mut ());
+}
+
+pub fn select_sort_observed<T, F, O>(nums: &mut [T], mut compare: F, observer: &mut O)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    let n = nums.len();
+    for i in 0..n.saturating_sub(1) {
         let mut min = i;
         for j in i + 1..n {
-            if nums[j as usize] < nums[min as usize] {
+            if stats::compare(&mut compare, observer, &nums[j], &nums[min]) == Ordering::Less {
                 min = j;
             }
         }
-        (nums[min as usize], nums[i as usize]) = (nums[i as usize], nums[min as usize]);
+        stats::swap(nums, min, i, observer);
     }
-    nums
+}
+
+pub fn select_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    select_sort_by(nums, |a, b| f(a).cmp(&f(b)));
 }
 
 #[cfg(test)]
@@ -28,4 +59,26 @@ mod tests {
         let nums = select_sort(nums);
         assert_eq!(nums, vec![]);
     }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        select_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        select_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "apple");
+        assert_eq!(words[4], "banana");
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        select_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
 }
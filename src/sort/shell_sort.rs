@@ -0,0 +1,176 @@
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GapSequence {
+    // n/2, n/4, ..., 1
+    Shell,
+    // 1, 4, 13, 40, ...
+    Knuth,
+    // 1, 4, 10, 23, 57, 132, 301, 701, then growing by 2.25
+    #[default]
+    Ciura,
+    // 1, 8, 23, 77, 281, ...
+    Sedgewick,
+}
+
+impl GapSequence {
+    // gaps below `n` from the largest down to 1
+    pub fn gaps(self, n: usize) -> Vec<usize> {
+        let mut gaps = vec![];
+        match self {
+            GapSequence::Shell => {
+                let mut gap = n / 2;
+                while gap > 0 {
+                    gaps.push(gap);
+                    gap /= 2;
+                }
+                gaps.reverse();
+            }
+            GapSequence::Knuth => {
+                let mut gap = 1;
+                while gap < n {
+                    gaps.push(gap);
+                    gap = 3 * gap + 1;
+                }
+            }
+            GapSequence::Ciura => {
+                for gap in [1, 4, 10, 23, 57, 132, 301, 701] {
+                    gaps.push(gap);
+                }
+                let mut gap = 701.0;
+                while (gap as usize) < n {
+                    gap *= 2.25;
+                    gaps.push(gap as usize);
+                }
+                gaps.retain(|&gap| gap < n);
+            }
+            GapSequence::Sedgewick => {
+                gaps.push(1);
+                let mut k = 1;
+                loop {
+                    // 4^k + 3 * 2^(k - 1) + 1
+                    let gap = (1 << (2 * k)) + 3 * (1 << (k - 1)) + 1;
+                    if gap >= n {
+                        break;
+                    }
+                    gaps.push(gap);
+                    k += 1;
+                }
+            }
+        }
+        if n < 2 {
+            gaps.clear();
+        }
+        gaps.reverse();
+        gaps
+    }
+}
+
+pub fn shell_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    shell_sort_slice(&mut nums);
+    nums
+}
+
+pub fn shell_sort_slice<T: Ord>(nums: &mut [T]) {
+    shell_sort_by(nums, T::cmp);
+}
+
+pub fn shell_sort_by<T, F>(nums: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let gaps = GapSequence::default().gaps(nums.len());
+    shell_sort_with_gaps(nums, &gaps, compare);
+}
+
+pub fn shell_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    shell_sort_by(nums, |a, b| f(a).cmp(&f(b)));
+}
+
+// `gaps` must be descending and end with 1 for the result to be sorted
+pub fn shell_sort_with_gaps<T, F>(nums: &mut [T], gaps: &[usize], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = nums.len();
+
+    for &gap in gaps {
+        // insertion sort over every gap-th element
+        for i in gap..n {
+            let mut j = i;
+            while j >= gap && compare(&nums[j - gap], &nums[j]) == Ordering::Greater {
+                nums.swap(j - gap, j);
+                j -= gap;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        let nums = shell_sort(nums);
+
+        assert_eq!(nums, vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let nums = vec![];
+        let nums = shell_sort(nums);
+        assert_eq!(nums, vec![]);
+    }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        shell_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        shell_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words[0], "fig");
+        assert_eq!(words[3], "apple");
+        assert_eq!(words[4], "banana");
+    }
+
+    #[test]
+    fn slice() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        shell_sort_slice(&mut nums[3..9]);
+
+        assert_eq!(nums, vec![6, 4, 2, 1, 3, 4, 7, 8, 9, 2, 3, 5]);
+    }
+
+    #[test]
+    fn gaps() {
+        assert_eq!(GapSequence::Shell.gaps(20), vec![10, 5, 2, 1]);
+        assert_eq!(GapSequence::Knuth.gaps(50), vec![40, 13, 4, 1]);
+        assert_eq!(GapSequence::Ciura.gaps(60), vec![57, 23, 10, 4, 1]);
+        assert_eq!(
+            GapSequence::Ciura.gaps(2000),
+            vec![1577, 701, 301, 132, 57, 23, 10, 4, 1]
+        );
+        assert_eq!(GapSequence::Sedgewick.gaps(300), vec![281, 77, 23, 8, 1]);
+        assert_eq!(GapSequence::Knuth.gaps(1), vec![]);
+
+        let expected: Vec<i32> = (0..3000).collect();
+        for sequence in [
+            GapSequence::Shell,
+            GapSequence::Knuth,
+            GapSequence::Ciura,
+            GapSequence::Sedgewick,
+        ] {
+            let mut nums: Vec<i32> = (0..3000).map(|i| (i * 7919) % 3000).collect();
+            shell_sort_with_gaps(&mut nums, &sequence.gaps(3000), i32::cmp);
+            assert_eq!(nums, expected);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::{cmp::Ordering, fmt};
+
+// Hooks called by the `*_sort_observed` functions while they run, every
+// method does nothing by default.
+pub trait SortObserver<T> {
+    fn compare(&mut self) {}
+
+    // after two elements of `nums` were swapped
+    fn swap(&mut self, _nums: &[T]) {}
+
+    // after `count` elements were written into `nums` from elsewhere
+    fn moves(&mut self, _count: usize, _nums: &[T]) {}
+
+    // a scratch buffer was allocated
+    fn alloc(&mut self) {}
+
+    fn enter(&mut self) {}
+
+    fn leave(&mut self) {}
+}
+
+// observes nothing, what the plain sorts use
+impl<T> SortObserver<T> for () {}
+
+type Trace<'a, T> = Box<dyn FnMut(&[T]) + 'a>;
+
+// Counts the work a sort does, optionally tracing the slice after each step.
+pub struct SortStats<'a, T> {
+    pub comparisons: usize,
+    pub swaps: usize,
+    pub moves: usize,
+    pub allocations: usize,
+    pub max_depth: usize,
+    depth: usize,
+    trace: Option<Trace<'a, T>>,
+}
+
+impl<'a, T> SortStats<'a, T> {
+    pub fn new() -> Self {
+        SortStats {
+            comparisons: 0,
+            swaps: 0,
+            moves: 0,
+            allocations: 0,
+            max_depth: 0,
+            depth: 0,
+            trace: None,
+        }
+    }
+
+    // `trace` receives the whole slice after every swap or move
+    pub fn with_trace(trace: impl FnMut(&[T]) + 'a) -> Self {
+        SortStats {
+            trace: Some(Box::new(trace)),
+            ..Self::new()
+        }
+    }
+
+    fn step(&mut self, nums: &[T]) {
+        if let Some(trace) = &mut self.trace {
+            trace(nums);
+        }
+    }
+}
+
+impl<T> Default for SortStats<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for SortStats<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SortStats")
+            .field("comparisons", &self.comparisons)
+            .field("swaps", &self.swaps)
+            .field("moves", &self.moves)
+            .field("allocations", &self.allocations)
+            .field("max_depth", &self.max_depth)
+            .finish()
+    }
+}
+
+impl<T> SortObserver<T> for SortStats<'_, T> {
+    fn compare(&mut self) {
+        self.comparisons += 1;
+    }
+
+    fn swap(&mut self, nums: &[T]) {
+        self.swaps += 1;
+        self.step(nums);
+    }
+
+    fn moves(&mut self, count: usize, nums: &[T]) {
+        self.moves += count;
+        self.step(nums);
+    }
+
+    fn alloc(&mut self) {
+        self.allocations += 1;
+    }
+
+    fn enter(&mut self) {
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+}
+
+// compare `a` with `b`, telling the observer
+pub(crate) fn compare<T, F, O>(compare: &mut F, observer: &mut O, a: &T, b: &T) -> Ordering
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T>,
+{
+    observer.compare();
+    compare(a, b)
+}
+
+// swap `nums[i]` with `nums[j]`, telling the observer; swapping an element
+// with itself changes nothing and isn't reported
+pub(crate) fn swap<T, O: SortObserver<T>>(nums: &mut [T], i: usize, j: usize, observer: &mut O) {
+    if i == j {
+        return;
+    }
+    nums.swap(i, j);
+    observer.swap(nums);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::{
+        bubble_sort::bubble_sort_observed, heap_sort::heap_sort_observed,
+        insert_sort::insert_sort_observed, merge_sort::merge_sort_observed,
+        quick_sort::quick_sort_observed, select_sort::select_sort_observed,
+    };
+
+    #[test]
+    fn sorted_input() {
+        let mut nums: Vec<i32> = (0..10).collect();
+
+        let mut stats = SortStats::new();
+        bubble_sort_observed(&mut nums, i32::cmp, &mut stats);
+        // one pass without any exchange
+        assert_eq!(stats.comparisons, 9);
+        assert_eq!(stats.swaps, 0);
+
+        let mut stats = SortStats::new();
+        insert_sort_observed(&mut nums, i32::cmp, &mut stats);
+        assert_eq!(stats.comparisons, 9);
+        assert_eq!(stats.swaps, 0);
+
+        let mut stats = SortStats::new();
+        select_sort_observed(&mut nums, i32::cmp, &mut stats);
+        assert_eq!(stats.comparisons, 45);
+        // every minimum is already in place, nothing is swapped with itself
+        assert_eq!(stats.swaps, 0);
+    }
+
+    #[test]
+    fn reversed_input() {
+        let mut nums: Vec<i32> = (0..10).rev().collect();
+        let mut stats = SortStats::new();
+        bubble_sort_observed(&mut nums, i32::cmp, &mut stats);
+        assert_eq!(nums, (0..10).collect::<Vec<_>>());
+        assert_eq!(stats.comparisons, 45);
+        assert_eq!(stats.swaps, 45);
+        assert_eq!(stats.allocations, 0);
+        assert_eq!(stats.max_depth, 0);
+    }
+
+    #[test]
+    fn recursion_and_allocations() {
+        let nums: Vec<i32> = (0..64).map(|i| (i * 37) % 64).collect();
+
+        let mut sorted = nums.clone();
+        let mut stats = SortStats::new();
+        merge_sort_observed(&mut sorted, i32::cmp, &mut stats);
+        assert_eq!(sorted, (0..64).collect::<Vec<_>>());
+        // one temp buffer per merge, 63 merges for 64 elements
+        assert_eq!(stats.allocations, 63);
+        assert_eq!(stats.max_depth, 6);
+        assert!(stats.moves > 0);
+
+        let mut sorted = nums.clone();
+        let mut stats = SortStats::new();
+        quick_sort_observed(&mut sorted, i32::cmp, &mut stats);
+        assert_eq!(sorted, (0..64).collect::<Vec<_>>());
+        assert_eq!(stats.allocations, 0);
+        assert!(stats.max_depth >= 1 && stats.max_depth <= 6);
+
+        let mut sorted = nums;
+        let mut stats = SortStats::new();
+        heap_sort_observed(&mut sorted, i32::cmp, &mut stats);
+        assert_eq!(sorted, (0..64).collect::<Vec<_>>());
+        assert!(stats.swaps >= 63);
+        assert_eq!(stats.max_depth, 0);
+    }
+
+    #[test]
+    fn trace() {
+        let mut steps = vec![];
+        let mut nums = vec![3, 1, 2];
+
+        let mut stats = SortStats::with_trace(|nums: &[i32]| steps.push(nums.to_vec()));
+        bubble_sort_observed(&mut nums, i32::cmp, &mut stats);
+        assert_eq!(stats.swaps, 2);
+        drop(stats);
+
+        assert_eq!(steps, vec![vec![1, 3, 2], vec![1, 2, 3]]);
+
+        // quick sort traces the whole slice, not the partition being sorted
+        let mut lens = vec![];
+        let mut nums: Vec<i32> = (0..20).rev().collect();
+        let mut stats = SortStats::with_trace(|nums: &[i32]| lens.push(nums.len()));
+        quick_sort_observed(&mut nums, i32::cmp, &mut stats);
+        drop(stats);
+        assert!(lens.iter().all(|&len| len == 20));
+
+        // with distinct values every traced step changes something
+        let input: Vec<i32> = (0..20).map(|i| (i * 7) % 20).collect();
+        let mut steps = vec![input.clone()];
+        let mut nums = input;
+        let mut stats = SortStats::with_trace(|nums: &[i32]| steps.push(nums.to_vec()));
+        quick_sort_observed(&mut nums, i32::cmp, &mut stats);
+        let swaps = stats.swaps;
+        drop(stats);
+        assert_eq!(steps.len(), swaps + 1);
+        assert!(steps.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+}
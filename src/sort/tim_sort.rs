@@ -0,0 +1,385 @@
+use std::cmp::Ordering;
+
+use crate::search::exponential_search::exponential_partition_point;
+
+// arrays shorter than this are sorted by a single binary insertion sort
+const MIN_MERGE: usize = 64;
+// consecutive wins of one run needed to enter galloping mode
+const MIN_GALLOP: usize = 7;
+
+pub fn tim_sort(mut nums: Vec<i32>) -> Vec<i32> {
+    tim_sort_slice(&mut nums);
+    nums
+}
+
+pub fn tim_sort_slice<T: Ord + Clone>(nums: &mut [T]) {
+    tim_sort_by(nums, T::cmp);
+}
+
+pub fn tim_sort_by<T, F>(nums: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = nums.len();
+    if n < 2 {
+        return;
+    }
+
+    let min_run = min_run(n);
+    // (start, len) of every pending run, bottom of the stack first
+    let mut runs: Vec<(usize, usize)> = vec![];
+    // one scratch buffer shared by every merge
+    let mut buf: Vec<T> = Vec::with_capacity(n / 2);
+    let mut min_gallop = MIN_GALLOP;
+
+    let mut start = 0;
+    while start < n {
+        let mut len = count_run(&mut nums[start..], &mut compare);
+
+        // extend short runs to min_run
+        if len < min_run {
+            let force = min_run.min(n - start);
+            binary_insert_sort(&mut nums[start..start + force], len, &mut compare);
+            len = force;
+        }
+
+        runs.push((start, len));
+        merge_collapse(nums, &mut runs, &mut buf, &mut compare, &mut min_gallop);
+
+        start += len;
+    }
+
+    while runs.len() > 1 {
+        let mut i = runs.len() - 2;
+        if i > 0 && runs[i - 1].1 < runs[i + 1].1 {
+            i -= 1;
+        }
+        merge_at(nums, &mut runs, i, &mut buf, &mut compare, &mut min_gallop);
+    }
+}
+
+pub fn tim_sort_by_key<T, K, F>(nums: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    tim_sort_by(nums, |a, b| f(a).cmp(&f(b)));
+}
+
+fn min_run(mut n: usize) -> usize {
+    let mut r = 0;
+    while n >= MIN_MERGE {
+        r |= n & 1;
+        n >>= 1;
+    }
+    n + r
+}
+
+// Length of the run at the head of `nums`, a strictly descending run is
+// reversed in place so every returned run is ascending.
+fn count_run<T, F>(nums: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = nums.len();
+    if n < 2 {
+        return n;
+    }
+
+    let mut end = 2;
+    if compare(&nums[1], &nums[0]) == Ordering::Less {
+        while end < n && compare(&nums[end], &nums[end - 1]) == Ordering::Less {
+            end += 1;
+        }
+        nums[..end].reverse();
+    } else {
+        while end < n && compare(&nums[end], &nums[end - 1]) != Ordering::Less {
+            end += 1;
+        }
+    }
+    end
+}
+
+// nums[..sorted] is already sorted
+fn binary_insert_sort<T, F>(nums: &mut [T], sorted: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for i in sorted.max(1)..nums.len() {
+        let (head, tail) = nums.split_at(i);
+        let pos = head.partition_point(|x| compare(x, &tail[0]) != Ordering::Greater);
+        nums[pos..=i].rotate_right(1);
+    }
+}
+
+// Keep run lengths growing at least as fast as the Fibonacci numbers from
+// the top of the stack down, so the stack stays O(log n) deep.
+fn merge_collapse<T, F>(
+    nums: &mut [T],
+    runs: &mut Vec<(usize, usize)>,
+    buf: &mut Vec<T>,
+    compare: &mut F,
+    min_gallop: &mut usize,
+) where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    while runs.len() > 1 {
+        let mut i = runs.len() - 2;
+        if (i > 0 && runs[i - 1].1 <= runs[i].1 + runs[i + 1].1)
+            || (i > 1 && runs[i - 2].1 <= runs[i - 1].1 + runs[i].1)
+        {
+            if runs[i - 1].1 < runs[i + 1].1 {
+                i -= 1;
+            }
+        } else if runs[i].1 > runs[i + 1].1 {
+            break;
+        }
+        merge_at(nums, runs, i, buf, compare, min_gallop);
+    }
+}
+
+// merge runs[i] with runs[i + 1]
+fn merge_at<T, F>(
+    nums: &mut [T],
+    runs: &mut Vec<(usize, usize)>,
+    i: usize,
+    buf: &mut Vec<T>,
+    compare: &mut F,
+    min_gallop: &mut usize,
+) where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let (base1, len1) = runs[i];
+    let (base2, len2) = runs[i + 1];
+    runs[i] = (base1, len1 + len2);
+    runs.remove(i + 1);
+
+    // elements of run1 not greater than run2[0] are already in place
+    let base1 = base1 + gallop_right(&nums[base2], &nums[base1..base2], compare);
+    if base1 == base2 {
+        return;
+    }
+
+    // elements of run2 not less than run1's last are already in place
+    let end = base2 + gallop_left(&nums[base2 - 1], &nums[base2..base2 + len2], compare);
+
+    let mid = base2 - base1;
+    let nums = &mut nums[base1..end];
+    if mid <= nums.len() - mid {
+        merge_lo(nums, mid, buf, compare, min_gallop);
+    } else {
+        merge_hi(nums, mid, buf, compare, min_gallop);
+    }
+}
+
+// Merge nums[..mid] and nums[mid..] front to back, buffering the left run.
+fn merge_lo<T, F>(
+    nums: &mut [T],
+    mid: usize,
+    buf: &mut Vec<T>,
+    compare: &mut F,
+    min_gallop: &mut usize,
+) where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    buf.clear();
+    buf.extend_from_slice(&nums[..mid]);
+
+    let (len_a, len_b) = (mid, nums.len());
+    // a indexes buf, b indexes the right run, k is the next slot to write
+    let (mut a, mut b, mut k) = (0, mid, 0);
+
+    while a < len_a && b < len_b {
+        let (mut wins_a, mut wins_b) = (0, 0);
+        while a < len_a && b < len_b {
+            if compare(&nums[b], &buf[a]) == Ordering::Less {
+                nums[k] = nums[b].clone();
+                b += 1;
+                wins_b += 1;
+                wins_a = 0;
+            } else {
+                nums[k] = buf[a].clone();
+                a += 1;
+                wins_a += 1;
+                wins_b = 0;
+            }
+            k += 1;
+            if wins_a >= *min_gallop || wins_b >= *min_gallop {
+                break;
+            }
+        }
+
+        // one run keeps winning, copy whole stretches of it at once
+        while a < len_a && b < len_b {
+            let count_a = gallop_right(&nums[b], &buf[a..], compare);
+            nums[k..k + count_a].clone_from_slice(&buf[a..a + count_a]);
+            a += count_a;
+            k += count_a;
+            if a == len_a {
+                break;
+            }
+
+            let count_b = gallop_left(&buf[a], &nums[b..], compare);
+            for x in 0..count_b {
+                nums[k + x] = nums[b + x].clone();
+            }
+            b += count_b;
+            k += count_b;
+
+            if count_a < MIN_GALLOP && count_b < MIN_GALLOP {
+                *min_gallop += 1;
+                break;
+            }
+            *min_gallop = min_gallop.saturating_sub(1).max(1);
+        }
+    }
+
+    // the rest of the right run is already in place
+    nums[k..k + (len_a - a)].clone_from_slice(&buf[a..]);
+}
+
+// Merge nums[..mid] and nums[mid..] back to front, buffering the right run.
+fn merge_hi<T, F>(
+    nums: &mut [T],
+    mid: usize,
+    buf: &mut Vec<T>,
+    compare: &mut F,
+    min_gallop: &mut usize,
+) where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    buf.clear();
+    buf.extend_from_slice(&nums[mid..]);
+
+    // nums[..a] and buf[..b] are left to merge into nums[..k]
+    let (mut a, mut b, mut k) = (mid, buf.len(), nums.len());
+
+    while a > 0 && b > 0 {
+        let (mut wins_a, mut wins_b) = (0, 0);
+        while a > 0 && b > 0 {
+            k -= 1;
+            if compare(&buf[b - 1], &nums[a - 1]) == Ordering::Less {
+                nums[k] = nums[a - 1].clone();
+                a -= 1;
+                wins_a += 1;
+                wins_b = 0;
+            } else {
+                nums[k] = buf[b - 1].clone();
+                b -= 1;
+                wins_b += 1;
+                wins_a = 0;
+            }
+            if wins_a >= *min_gallop || wins_b >= *min_gallop {
+                break;
+            }
+        }
+
+        while a > 0 && b > 0 {
+            let pos = gallop_right(&buf[b - 1], &nums[..a], compare);
+            let count_a = a - pos;
+            for x in (0..count_a).rev() {
+                nums[k - count_a + x] = nums[pos + x].clone();
+            }
+            a = pos;
+            k -= count_a;
+            if a == 0 {
+                break;
+            }
+
+            let pos = gallop_left(&nums[a - 1], &buf[..b], compare);
+            let count_b = b - pos;
+            nums[k - count_b..k].clone_from_slice(&buf[pos..b]);
+            b = pos;
+            k -= count_b;
+
+            if count_a < MIN_GALLOP && count_b < MIN_GALLOP {
+                *min_gallop += 1;
+                break;
+            }
+            *min_gallop = min_gallop.saturating_sub(1).max(1);
+        }
+    }
+
+    // the rest of the left run is already in place
+    nums[..b].clone_from_slice(&buf[..b]);
+}
+
+// first index of `run` whose element is not less than `key`
+fn gallop_left<T, F>(key: &T, run: &[T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    exponential_partition_point(run, |x| compare(x, key) == Ordering::Less)
+}
+
+// first index of `run` whose element is greater than `key`
+fn gallop_right<T, F>(key: &T, run: &[T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    exponential_partition_point(run, |x| compare(x, key) != Ordering::Greater)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+
+        let nums = tim_sort(nums);
+
+        assert_eq!(nums, vec![1, 2, 2, 3, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let nums = vec![];
+        let nums = tim_sort(nums);
+        assert_eq!(nums, vec![]);
+    }
+
+    #[test]
+    fn by() {
+        let mut nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        tim_sort_by(&mut nums, |a, b| b.cmp(a));
+        assert_eq!(nums, vec![9, 8, 7, 6, 5, 4, 4, 3, 3, 2, 2, 1]);
+
+        let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+        tim_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words, vec!["fig", "pear", "kiwi", "apple", "banana"]);
+    }
+
+    #[test]
+    fn runs() {
+        // ascending and descending runs long enough to be merged, not insert sorted
+        let mut nums: Vec<i32> = (0..500)
+            .chain((0..300).rev())
+            .chain((250..1000).step_by(3))
+            .chain((0..700).rev().step_by(2))
+            .collect();
+        let mut expected = nums.clone();
+        expected.sort();
+
+        tim_sort_slice(&mut nums);
+
+        assert_eq!(nums, expected);
+    }
+
+    #[test]
+    fn stable() {
+        // (key, original index) pairs, sorted by key only
+        let mut pairs: Vec<(i32, usize)> =
+            (0..2000).map(|i| ((i * 37 % 101) as i32 / 10, i)).collect();
+        let mut expected = pairs.clone();
+        expected.sort_by_key(|p| p.0);
+
+        tim_sort_by_key(&mut pairs, |p| p.0);
+
+        assert_eq!(pairs, expected);
+    }
+}
@@ -1,2 +1,130 @@
 pub mod array_stack;
-pub mod link_stack;
\ No newline at end of file
+pub mod expr;
+pub mod history;
+pub mod link_stack;
+pub mod min_stack;
+pub mod monotonic_stack;
+pub mod persistent_stack;
+
+// Last in, first out collection, implemented by every stack backing so code
+// can be written once for all of them.
+pub trait Stack<T> {
+    // gives `val` back if the stack is full
+    fn push(&mut self, val: T) -> Result<(), T>;
+
+    fn pop(&mut self) -> Option<T>;
+
+    fn peek(&self) -> Option<&T>;
+
+    fn size(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    // elements from the top down
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use array_stack::ArrayStack;
+    use link_stack::LinkStack;
+    use min_stack::{MaxStack, MinStack};
+
+    // Scenarios every implementation has to pass, `new` makes an empty stack
+    // with room for at least 1000 elements.
+    fn conformance<S: Stack<i32>>(new: impl Fn() -> S) {
+        // empty
+        let mut stack = new();
+        assert!(stack.is_empty());
+        assert_eq!(stack.size(), 0);
+        assert_eq!(stack.peek(), None);
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.to_vec(), vec![]);
+
+        // last in, first out
+        let mut stack = new();
+        for i in 0..10 {
+            assert_eq!(stack.push(i), Ok(()));
+            assert_eq!(stack.peek(), Some(&i));
+            assert_eq!(stack.size(), i as usize + 1);
+        }
+        assert_eq!(stack.to_vec(), (0..10).rev().collect::<Vec<_>>());
+        for i in (0..10).rev() {
+            assert_eq!(stack.pop(), Some(i));
+        }
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+
+        // peek leaves the stack alone
+        let mut stack = new();
+        stack.push(7).unwrap();
+        assert_eq!(stack.peek(), Some(&7));
+        assert_eq!(stack.peek(), Some(&7));
+        assert_eq!(stack.size(), 1);
+
+        // usable again after being emptied
+        stack.pop();
+        stack.push(8).unwrap();
+        assert_eq!(stack.to_vec(), vec![8]);
+
+        // interleaved pushes and pops against a vec as the model
+        let mut stack = new();
+        let mut model = vec![];
+        for i in 0..1000 {
+            if i % 3 == 2 {
+                assert_eq!(stack.pop(), model.pop());
+            } else {
+                stack.push(i).unwrap();
+                model.push(i);
+            }
+            assert_eq!(stack.peek(), model.last());
+            assert_eq!(stack.size(), model.len());
+        }
+        assert_eq!(
+            stack.to_vec(),
+            model.iter().rev().copied().collect::<Vec<_>>()
+        );
+    }
+
+    // only uses the trait, works for any backing
+    fn reverse<S: Stack<i32>>(stack: &mut S, nums: &[i32]) -> Vec<i32> {
+        for &num in nums {
+            stack.push(num).unwrap();
+        }
+        let mut reversed = vec![];
+        while let Some(num) = stack.pop() {
+            reversed.push(num);
+        }
+        reversed
+    }
+
+    #[test]
+    fn array_stack() {
+        conformance(|| ArrayStack::new(1000));
+        conformance(|| ArrayStack::growable(1));
+        assert_eq!(reverse(&mut ArrayStack::new(3), &[1, 2, 3]), vec![3, 2, 1]);
+
+        // full, the value comes back through the trait
+        let mut stack = ArrayStack::new(1);
+        assert_eq!(Stack::push(&mut stack, 1), Ok(()));
+        assert_eq!(Stack::push(&mut stack, 2), Err(2));
+        assert_eq!(Stack::to_vec(&stack), [1]);
+    }
+
+    #[test]
+    fn link_stack() {
+        conformance(LinkStack::new);
+        assert_eq!(reverse(&mut LinkStack::new(), &[1, 2, 3]), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn min_max_stack() {
+        conformance(MinStack::new);
+        conformance(MaxStack::new);
+    }
+}
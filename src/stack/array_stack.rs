@@ -1,68 +1,106 @@
+use super::Stack;
+
 pub struct ArrayStack<T> {
+    // elements bottom first, never longer than `capacity`
     array: Vec<T>,
-    top: usize,
     capacity: usize,
+    // double the capacity instead of refusing a push when full
+    grow: bool,
 }
 
-impl<T: Default + Clone> ArrayStack<T> {
+impl<T> ArrayStack<T> {
+    // a stack holding at most `capacity` elements
     pub fn new(capacity: usize) -> Self {
         ArrayStack {
-            array: vec![Default::default(); capacity],
-            top: 0,
+            array: Vec::with_capacity(capacity),
             capacity,
+            grow: false,
         }
     }
 
-    pub fn push(&mut self, val: T) {
-        if self.is_full() {
-            println!("Stack fulled.");
-            return;
+    // a stack starting with room for `capacity` elements that doubles its
+    // capacity whenever a push finds it full
+    pub fn growable(capacity: usize) -> Self {
+        ArrayStack {
+            grow: true,
+            ..Self::new(capacity)
         }
+    }
 
-        self.array[self.top] = val;
-        self.top += 1;
+    // gives `val` back if the stack is full and can't grow
+    pub fn push(&mut self, val: T) -> Result<(), T> {
+        self.try_push(val)
     }
 
-    pub fn pop(&mut self) -> Option<T> {
-        if self.is_empty() {
-            None
-        } else {
-            self.top -= 1;
-            Some(self.array[self.top].clone())
+    // same as `push`
+    pub fn try_push(&mut self, val: T) -> Result<(), T> {
+        if self.size() == self.capacity {
+            if !self.grow {
+                return Err(val);
+            }
+            self.capacity = (self.capacity * 2).max(1);
+            self.array.reserve_exact(self.capacity - self.size());
         }
+
+        self.array.push(val);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.array.pop()
     }
 
     pub fn peek(&self) -> Option<&T> {
-        if self.is_empty() {
-            None
-        } else {
-            Some(&self.array[self.top - 1])
-        }
+        self.array.last()
     }
 
     pub fn is_empty(&self) -> bool {
         self.size() == 0
     }
 
+    // only a stack that can't grow is ever full
     pub fn is_full(&self) -> bool {
-        self.size() == self.capacity()
+        !self.grow && self.size() == self.capacity()
     }
 
     pub fn size(&self) -> usize {
-        self.top
+        self.array.len()
     }
 
     pub fn capacity(&self) -> usize {
         self.capacity
     }
 
-    pub fn to_vec(&self) -> Vec<T> {
-        let mut result = vec![Default::default(); self.size()];
-        for i in 0..self.size() {
-            let j = self.size() - i - 1;
-            result[i] = self.array[j].clone();
-        }
-        result
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.array.iter().rev().cloned().collect()
+    }
+}
+
+impl<T> Stack<T> for ArrayStack<T> {
+    fn push(&mut self, val: T) -> Result<(), T> {
+        self.try_push(val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        ArrayStack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        ArrayStack::peek(self)
+    }
+
+    fn size(&self) -> usize {
+        ArrayStack::size(self)
+    }
+
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        ArrayStack::to_vec(self)
     }
 }
 
@@ -73,9 +111,9 @@ mod tests {
     #[test]
     fn push_pop() {
         let mut stack = ArrayStack::new(10);
-        stack.push(1);
-        stack.push(2);
-        stack.push(3);
+        stack.push(1).unwrap();
+        stack.push(2).unwrap();
+        stack.push(3).unwrap();
 
         assert_eq!(stack.size(), 3);
         assert!(!stack.is_empty());
@@ -95,4 +133,65 @@ mod tests {
         assert_eq!(stack.size(), 0);
         assert!(stack.is_empty());
     }
+
+    #[test]
+    fn try_push() {
+        let mut stack = ArrayStack::new(2);
+        assert_eq!(stack.try_push(1), Ok(()));
+        assert_eq!(stack.try_push(2), Ok(()));
+        assert!(stack.is_full());
+
+        // the value comes back and the stack is unchanged
+        assert_eq!(stack.try_push(3), Err(3));
+        assert_eq!(stack.to_vec(), [2, 1]);
+        assert_eq!(stack.capacity(), 2);
+
+        stack.pop();
+        assert_eq!(stack.try_push(3), Ok(()));
+        assert_eq!(stack.to_vec(), [3, 1]);
+    }
+
+    #[test]
+    fn push_full() {
+        let mut stack = ArrayStack::new(1);
+        assert_eq!(stack.push(1), Ok(()));
+        assert_eq!(stack.push(2), Err(2));
+        assert_eq!(stack.to_vec(), [1]);
+    }
+
+    #[test]
+    fn growable() {
+        let mut stack = ArrayStack::growable(2);
+        let mut capacities = vec![];
+        for i in 0..9 {
+            assert_eq!(stack.try_push(i), Ok(()));
+            capacities.push(stack.capacity());
+        }
+        assert_eq!(capacities, [2, 2, 4, 4, 8, 8, 8, 8, 16]);
+        assert!(!stack.is_full());
+        assert_eq!(stack.to_vec(), (0..9).rev().collect::<Vec<_>>());
+
+        // even from nothing
+        let mut stack = ArrayStack::growable(0);
+        stack.push(1).unwrap();
+        stack.push(2).unwrap();
+        assert_eq!(stack.capacity(), 2);
+        assert_eq!(stack.pop(), Some(2));
+    }
+
+    #[test]
+    fn moves_out() {
+        // neither Default nor Clone
+        #[derive(Debug, PartialEq)]
+        struct Token(String);
+
+        let mut stack = ArrayStack::new(2);
+        stack.push(Token("a".to_string())).unwrap();
+        stack.push(Token("b".to_string())).unwrap();
+
+        assert_eq!(stack.peek(), Some(&Token("b".to_string())));
+        assert_eq!(stack.pop(), Some(Token("b".to_string())));
+        assert_eq!(stack.pop(), Some(Token("a".to_string())));
+        assert_eq!(stack.pop(), None);
+    }
 }
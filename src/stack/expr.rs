@@ -0,0 +1,657 @@
+// Infix arithmetic: tokenize, convert to reverse polish notation with the
+// shunting-yard algorithm, then evaluate the RPN on a stack.
+//
+// Positions in errors are byte offsets into the input.
+
+use std::{error::Error, fmt};
+
+use super::{array_stack::ArrayStack, link_stack::LinkStack};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Num(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Caret,
+    LParen,
+    RParen,
+    Comma,
+}
+
+// a value together with where it starts in the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub pos: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+impl BinOp {
+    fn precedence(self) -> u8 {
+        match self {
+            BinOp::Add | BinOp::Sub => 1,
+            BinOp::Mul | BinOp::Div | BinOp::Rem => 2,
+            BinOp::Pow => 4,
+        }
+    }
+
+    fn right_assoc(self) -> bool {
+        self == BinOp::Pow
+    }
+
+    fn apply(self, a: f64, b: f64) -> f64 {
+        match self {
+            BinOp::Add => a + b,
+            BinOp::Sub => a - b,
+            BinOp::Mul => a * b,
+            BinOp::Div => a / b,
+            BinOp::Rem => a % b,
+            BinOp::Pow => a.powf(b),
+        }
+    }
+}
+
+// unary minus binds tighter than `*` but looser than `^`, so -2^2 is -4
+const NEG_PRECEDENCE: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Func {
+    Sin,
+    Cos,
+    Tan,
+    Sqrt,
+    Abs,
+    Ln,
+    Log,
+    Exp,
+    Min,
+    Max,
+}
+
+impl Func {
+    const ALL: [Func; 10] = [
+        Func::Sin,
+        Func::Cos,
+        Func::Tan,
+        Func::Sqrt,
+        Func::Abs,
+        Func::Ln,
+        Func::Log,
+        Func::Exp,
+        Func::Min,
+        Func::Max,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Func::Sin => "sin",
+            Func::Cos => "cos",
+            Func::Tan => "tan",
+            Func::Sqrt => "sqrt",
+            Func::Abs => "abs",
+            Func::Ln => "ln",
+            Func::Log => "log",
+            Func::Exp => "exp",
+            Func::Min => "min",
+            Func::Max => "max",
+        }
+    }
+
+    pub fn arity(self) -> usize {
+        match self {
+            Func::Min | Func::Max => 2,
+            _ => 1,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Func> {
+        Func::ALL.into_iter().find(|func| func.name() == name)
+    }
+
+    // `args` holds exactly `arity` values
+    fn apply(self, args: &[f64]) -> f64 {
+        match self {
+            Func::Sin => args[0].sin(),
+            Func::Cos => args[0].cos(),
+            Func::Tan => args[0].tan(),
+            Func::Sqrt => args[0].sqrt(),
+            Func::Abs => args[0].abs(),
+            Func::Ln => args[0].ln(),
+            Func::Log => args[0].log10(),
+            Func::Exp => args[0].exp(),
+            Func::Min => args[0].min(args[1]),
+            Func::Max => args[0].max(args[1]),
+        }
+    }
+}
+
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rpn {
+    Num(f64),
+    Neg,
+    Binary(BinOp),
+    Func(Func),
+}
+
+impl fmt::Display for Rpn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rpn::Num(num) => write!(f, "{}", num),
+            Rpn::Neg => write!(f, "neg"),
+            Rpn::Binary(op) => {
+                let symbol = match op {
+                    BinOp::Add => "+",
+                    BinOp::Sub => "-",
+                    BinOp::Mul => "*",
+                    BinOp::Div => "/",
+                    BinOp::Rem => "%",
+                    BinOp::Pow => "^",
+                };
+                write!(f, "{}", symbol)
+            }
+            Rpn::Func(func) => write!(f, "{}", func.name()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprError {
+    // no token starts with `ch`
+    BadChar {
+        pos: usize,
+        ch: char,
+    },
+    // digits and dots that aren't a number, e.g. `1.2.3`
+    BadNumber {
+        pos: usize,
+    },
+    // neither a function nor a constant
+    UnknownName {
+        pos: usize,
+        name: String,
+    },
+    // a function name not followed by `(`
+    ExpectedParen {
+        pos: usize,
+    },
+    // `(` never closed or `)` never opened
+    UnbalancedParen {
+        pos: usize,
+    },
+    // an operator, `)` or the end of input where an operand should be
+    MissingOperand {
+        pos: usize,
+    },
+    // an operand or `(` right after another operand
+    UnexpectedOperand {
+        pos: usize,
+    },
+    // `,` outside of a function's arguments
+    MisplacedComma {
+        pos: usize,
+    },
+    Arity {
+        pos: usize,
+        func: Func,
+        found: usize,
+    },
+    Empty,
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprError::BadChar { pos, ch } => write!(f, "unexpected {:?} at {}", ch, pos),
+            ExprError::BadNumber { pos } => write!(f, "malformed number at {}", pos),
+            ExprError::UnknownName { pos, name } => write!(f, "unknown name {} at {}", name, pos),
+            ExprError::ExpectedParen { pos } => write!(f, "expected ( after function at {}", pos),
+            ExprError::UnbalancedParen { pos } => write!(f, "unbalanced parenthesis at {}", pos),
+            ExprError::MissingOperand { pos } => write!(f, "missing operand at {}", pos),
+            ExprError::UnexpectedOperand { pos } => write!(f, "missing operator at {}", pos),
+            ExprError::MisplacedComma { pos } => write!(f, "comma outside of a call at {}", pos),
+            ExprError::Arity { pos, func, found } => write!(
+                f,
+                "{} takes {} arguments, got {} at {}",
+                func.name(),
+                func.arity(),
+                found,
+                pos
+            ),
+            ExprError::Empty => write!(f, "empty expression"),
+        }
+    }
+}
+
+impl Error for ExprError {}
+
+pub fn tokenize(input: &str) -> Result<Vec<Spanned<Token>>, ExprError> {
+    let mut tokens = vec![];
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((pos, ch)) = chars.next() {
+        let token = match ch {
+            _ if ch.is_whitespace() => continue,
+            '0'..='9' | '.' => {
+                let mut end = pos + 1;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    end = i + 1;
+                    chars.next();
+                }
+                let num = input[pos..end]
+                    .parse()
+                    .map_err(|_| ExprError::BadNumber { pos })?;
+                Token::Num(num)
+            }
+            _ if ch.is_alphabetic() || ch == '_' => {
+                let mut end = pos + ch.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                Token::Ident(input[pos..end].to_string())
+            }
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '^' => Token::Caret,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            _ => return Err(ExprError::BadChar { pos, ch }),
+        };
+        tokens.push(Spanned { value: token, pos });
+    }
+
+    Ok(tokens)
+}
+
+// waiting on the operator stack
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pending {
+    Binary(BinOp),
+    Neg,
+    Func(Func),
+    LParen,
+}
+
+// Shunting-yard, checking along the way that operands and operators take
+// turns and that parentheses and function arguments line up.
+pub fn to_rpn(tokens: &[Spanned<Token>]) -> Result<Vec<Spanned<Rpn>>, ExprError> {
+    let mut output = vec![];
+    let mut ops: LinkStack<Spanned<Pending>> = LinkStack::new();
+    // arguments seen so far by each open call, innermost on top
+    let mut args: ArrayStack<usize> = ArrayStack::growable(4);
+    // an operand has to come next, as at the start or after an operator
+    let mut expect_operand = true;
+
+    let Some(last) = tokens.last() else {
+        return Err(ExprError::Empty);
+    };
+    let end = last.pos + 1;
+
+    let mut iter = tokens.iter().peekable();
+    while let Some(Spanned { value: token, pos }) = iter.next() {
+        let pos = *pos;
+        match token {
+            Token::Num(_) | Token::Ident(_) | Token::LParen if !expect_operand => {
+                return Err(ExprError::UnexpectedOperand { pos });
+            }
+            Token::Num(num) => {
+                output.push(Spanned {
+                    value: Rpn::Num(*num),
+                    pos,
+                });
+                expect_operand = false;
+            }
+            Token::Ident(name) => {
+                if let Some(func) = Func::from_name(name) {
+                    if !matches!(
+                        iter.peek(),
+                        Some(Spanned {
+                            value: Token::LParen,
+                            ..
+                        })
+                    ) {
+                        return Err(ExprError::ExpectedParen { pos });
+                    }
+                    ops.push(Spanned {
+                        value: Pending::Func(func),
+                        pos,
+                    });
+                } else if let Some(num) = constant(name) {
+                    output.push(Spanned {
+                        value: Rpn::Num(num),
+                        pos,
+                    });
+                    expect_operand = false;
+                } else {
+                    return Err(ExprError::UnknownName {
+                        pos,
+                        name: name.clone(),
+                    });
+                }
+            }
+            Token::LParen => {
+                let call = matches!(
+                    ops.peek(),
+                    Some(Spanned {
+                        value: Pending::Func(_),
+                        ..
+                    })
+                );
+                if call {
+                    args.push(1).unwrap();
+                }
+                ops.push(Spanned {
+                    value: Pending::LParen,
+                    pos,
+                });
+            }
+            Token::RParen | Token::Comma if expect_operand => {
+                return Err(ExprError::MissingOperand { pos });
+            }
+            Token::RParen => {
+                pop_to_paren(&mut ops, &mut output).ok_or(ExprError::UnbalancedParen { pos })?;
+                if let Some(Spanned {
+                    value: Pending::Func(func),
+                    pos,
+                }) = ops.peek().cloned()
+                {
+                    ops.pop();
+                    let found = args.pop().unwrap();
+                    if found != func.arity() {
+                        return Err(ExprError::Arity { pos, func, found });
+                    }
+                    output.push(Spanned {
+                        value: Rpn::Func(func),
+                        pos,
+                    });
+                }
+            }
+            Token::Comma => {
+                // the `(` stays, more arguments follow
+                let paren = pop_to_paren(&mut ops, &mut output);
+                let in_call = matches!(
+                    ops.peek(),
+                    Some(Spanned {
+                        value: Pending::Func(_),
+                        ..
+                    })
+                );
+                if paren.is_none() || !in_call {
+                    return Err(ExprError::MisplacedComma { pos });
+                }
+                ops.push(paren.unwrap());
+                let count = args.pop().unwrap();
+                args.push(count + 1).unwrap();
+                expect_operand = true;
+            }
+            Token::Minus if expect_operand => ops.push(Spanned {
+                value: Pending::Neg,
+                pos,
+            }),
+            // unary plus changes nothing
+            Token::Plus if expect_operand => {}
+            _ if expect_operand => return Err(ExprError::MissingOperand { pos }),
+            Token::Plus
+            | Token::Minus
+            | Token::Star
+            | Token::Slash
+            | Token::Percent
+            | Token::Caret => {
+                let op = match token {
+                    Token::Plus => BinOp::Add,
+                    Token::Minus => BinOp::Sub,
+                    Token::Star => BinOp::Mul,
+                    Token::Slash => BinOp::Div,
+                    Token::Percent => BinOp::Rem,
+                    _ => BinOp::Pow,
+                };
+
+                // pop everything binding at least as tight, strictly tighter
+                // for right associative operators
+                while let Some(top) = ops.peek() {
+                    let precedence = match top.value {
+                        Pending::Binary(top_op) => top_op.precedence(),
+                        Pending::Neg => NEG_PRECEDENCE,
+                        Pending::Func(_) | Pending::LParen => break,
+                    };
+                    if precedence < op.precedence()
+                        || (precedence == op.precedence() && op.right_assoc())
+                    {
+                        break;
+                    }
+                    output.push(resolve(ops.pop().unwrap()));
+                }
+
+                ops.push(Spanned {
+                    value: Pending::Binary(op),
+                    pos,
+                });
+                expect_operand = true;
+            }
+        }
+    }
+
+    if expect_operand {
+        return Err(ExprError::MissingOperand { pos: end });
+    }
+    while let Some(op) = ops.pop() {
+        if op.value == Pending::LParen {
+            return Err(ExprError::UnbalancedParen { pos: op.pos });
+        }
+        output.push(resolve(op));
+    }
+
+    Ok(output)
+}
+
+// Move operators to the output down to the nearest `(`, which is popped and
+// returned, None if there is no `(`.
+fn pop_to_paren(
+    ops: &mut LinkStack<Spanned<Pending>>,
+    output: &mut Vec<Spanned<Rpn>>,
+) -> Option<Spanned<Pending>> {
+    while let Some(op) = ops.pop() {
+        if op.value == Pending::LParen {
+            return Some(op);
+        }
+        output.push(resolve(op));
+    }
+    None
+}
+
+// an operator leaving the stack for the output, never a `(`
+fn resolve(op: Spanned<Pending>) -> Spanned<Rpn> {
+    let value = match op.value {
+        Pending::Binary(op) => Rpn::Binary(op),
+        Pending::Neg => Rpn::Neg,
+        Pending::Func(func) => Rpn::Func(func),
+        Pending::LParen => unreachable!("( never reaches the output"),
+    };
+    Spanned { value, pos: op.pos }
+}
+
+pub fn eval_rpn(rpn: &[Spanned<Rpn>]) -> Result<f64, ExprError> {
+    let mut values: ArrayStack<f64> = ArrayStack::growable(rpn.len());
+
+    for Spanned { value: item, pos } in rpn {
+        let pos = *pos;
+        let mut operand = || values.pop().ok_or(ExprError::MissingOperand { pos });
+        let result = match item {
+            Rpn::Num(num) => *num,
+            Rpn::Neg => -operand()?,
+            Rpn::Binary(op) => {
+                let b = operand()?;
+                let a = operand()?;
+                op.apply(a, b)
+            }
+            Rpn::Func(func) => {
+                let mut args = vec![0.0; func.arity()];
+                for arg in args.iter_mut().rev() {
+                    *arg = operand()?;
+                }
+                func.apply(&args)
+            }
+        };
+        values.push(result).unwrap();
+    }
+
+    let result = values.pop().ok_or(ExprError::Empty)?;
+    if !values.is_empty() {
+        // more than one value left, an operator is missing before the last
+        let pos = rpn.last().map_or(0, |item| item.pos);
+        return Err(ExprError::UnexpectedOperand { pos });
+    }
+    Ok(result)
+}
+
+pub fn eval(input: &str) -> Result<f64, ExprError> {
+    eval_rpn(&to_rpn(&tokenize(input)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpn(input: &str) -> String {
+        let rpn = to_rpn(&tokenize(input).unwrap()).unwrap();
+        let items: Vec<String> = rpn.iter().map(|item| item.value.to_string()).collect();
+        items.join(" ")
+    }
+
+    #[test]
+    fn tokens() {
+        let tokens = tokenize("max(1.5, x_1) ^2").unwrap();
+        let values: Vec<Token> = tokens.iter().map(|t| t.value.clone()).collect();
+        assert_eq!(
+            values,
+            vec![
+                Token::Ident("max".to_string()),
+                Token::LParen,
+                Token::Num(1.5),
+                Token::Comma,
+                Token::Ident("x_1".to_string()),
+                Token::RParen,
+                Token::Caret,
+                Token::Num(2.0),
+            ]
+        );
+        let positions: Vec<usize> = tokens.iter().map(|t| t.pos).collect();
+        assert_eq!(positions, vec![0, 3, 4, 7, 9, 12, 14, 15]);
+    }
+
+    #[test]
+    fn shunting_yard() {
+        assert_eq!(
+            rpn("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3"),
+            "3 4 2 * 1 5 - 2 3 ^ ^ / +"
+        );
+        assert_eq!(rpn("8 - 3 - 2"), "8 3 - 2 -");
+        assert_eq!(rpn("2 ^ 3 ^ 2"), "2 3 2 ^ ^");
+        assert_eq!(rpn("-2 ^ 2"), "2 2 ^ neg");
+        assert_eq!(rpn("2 * -3"), "2 3 neg *");
+        assert_eq!(rpn("max(1, 2 + 3) * 4"), "1 2 3 + max 4 *");
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(eval("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3"), Ok(3.0001220703125));
+        assert_eq!(eval("8 - 3 - 2"), Ok(3.0));
+        assert_eq!(eval("8 / 4 / 2"), Ok(1.0));
+        assert_eq!(eval("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(eval("7 % 4"), Ok(3.0));
+
+        // unary minus
+        assert_eq!(eval("-2 ^ 2"), Ok(-4.0));
+        assert_eq!(eval("2 ^ -1"), Ok(0.5));
+        assert_eq!(eval("--3"), Ok(3.0));
+        assert_eq!(eval("-(2 + 3) * +2"), Ok(-10.0));
+
+        // functions and constants
+        assert_eq!(eval("sqrt(16) + abs(-2)"), Ok(6.0));
+        assert_eq!(eval("min(max(1, 2), 3 * 5)"), Ok(2.0));
+        assert_eq!(eval("cos(0) + ln(e) + log(100)"), Ok(4.0));
+        assert!((eval("sin(pi / 2)").unwrap() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn errors() {
+        use ExprError::*;
+
+        assert_eq!(eval("(1 + 2"), Err(UnbalancedParen { pos: 0 }));
+        assert_eq!(eval("1 + 2)"), Err(UnbalancedParen { pos: 5 }));
+        assert_eq!(eval("1 + $"), Err(BadChar { pos: 4, ch: '$' }));
+        assert_eq!(eval("1.2.3"), Err(BadNumber { pos: 0 }));
+        assert_eq!(eval("1 +"), Err(MissingOperand { pos: 3 }));
+        assert_eq!(eval("* 2"), Err(MissingOperand { pos: 0 }));
+        assert_eq!(eval("()"), Err(MissingOperand { pos: 1 }));
+        assert_eq!(eval("1 2"), Err(UnexpectedOperand { pos: 2 }));
+        assert_eq!(eval("2 (3)"), Err(UnexpectedOperand { pos: 2 }));
+        assert_eq!(
+            eval("foo(1)"),
+            Err(UnknownName {
+                pos: 0,
+                name: "foo".to_string()
+            })
+        );
+        assert_eq!(eval("sin 1"), Err(ExpectedParen { pos: 0 }));
+        assert_eq!(
+            eval("1 + max(1)"),
+            Err(Arity {
+                pos: 4,
+                func: Func::Max,
+                found: 1
+            })
+        );
+        assert_eq!(eval("1, 2"), Err(MisplacedComma { pos: 1 }));
+        assert_eq!(eval("max((1, 2), 3)"), Err(MisplacedComma { pos: 6 }));
+        assert_eq!(eval("  "), Err(Empty));
+
+        assert_eq!(
+            eval("(1 + 2").unwrap_err().to_string(),
+            "unbalanced parenthesis at 0"
+        );
+    }
+
+    #[test]
+    fn bad_rpn() {
+        let item = |value, pos| Spanned { value, pos };
+        let rpn = [item(Rpn::Num(1.0), 0), item(Rpn::Binary(BinOp::Add), 1)];
+        assert_eq!(eval_rpn(&rpn), Err(ExprError::MissingOperand { pos: 1 }));
+
+        let rpn = [item(Rpn::Num(1.0), 0), item(Rpn::Num(2.0), 2)];
+        assert_eq!(eval_rpn(&rpn), Err(ExprError::UnexpectedOperand { pos: 2 }));
+
+        assert_eq!(eval_rpn(&[]), Err(ExprError::Empty));
+    }
+}
@@ -0,0 +1,505 @@
+// Undo/redo on two stacks: executed commands go on the undo stack, undoing
+// moves them to the redo stack and redoing moves them back. Recording a new
+// step throws the redo stack away.
+
+use super::link_stack::LinkStack;
+
+// A reversible change to a `Target`. `revert` right after `apply` must
+// restore the target to how it was.
+pub trait Command {
+    type Target;
+
+    fn apply(&mut self, target: &mut Self::Target);
+
+    fn revert(&mut self, target: &mut Self::Target);
+
+    // shown in menus like "Undo <label>"
+    fn label(&self) -> &str;
+}
+
+// one undo step, either a single command or a whole transaction
+struct Entry<C> {
+    label: String,
+    commands: Vec<C>,
+}
+
+impl<C: Command> Entry<C> {
+    fn apply(&mut self, target: &mut C::Target) {
+        for command in self.commands.iter_mut() {
+            command.apply(target);
+        }
+    }
+
+    fn revert(&mut self, target: &mut C::Target) {
+        for command in self.commands.iter_mut().rev() {
+            command.revert(target);
+        }
+    }
+}
+
+// commands executed since `begin`, not yet an entry
+struct Transaction<C> {
+    label: String,
+    commands: Vec<C>,
+    // nested `begin`s still waiting for their `commit`
+    depth: usize,
+}
+
+pub struct History<C> {
+    undo: LinkStack<Entry<C>>,
+    redo: LinkStack<Entry<C>>,
+    capacity: usize,
+    // Entries at the bottom of `undo` already evicted. A stack can't drop its
+    // bottom cheaply, so they are removed in one go once there are
+    // `capacity` of them, keeping pushes amortized O(1).
+    evicted: usize,
+    transaction: Option<Transaction<C>>,
+}
+
+impl<C: Command> History<C> {
+    // keeps at most `capacity` undo steps, forgetting the oldest first
+    pub fn new(capacity: usize) -> Self {
+        History {
+            undo: LinkStack::new(),
+            redo: LinkStack::new(),
+            capacity,
+            evicted: 0,
+            transaction: None,
+        }
+    }
+
+    // Apply `command` to `target` and record it, as its own undo step or as
+    // part of the open transaction.
+    pub fn execute(&mut self, mut command: C, target: &mut C::Target) {
+        command.apply(target);
+
+        match self.transaction.as_mut() {
+            Some(transaction) => transaction.commands.push(command),
+            None => self.record(Entry {
+                label: command.label().to_string(),
+                commands: vec![command],
+            }),
+        }
+    }
+
+    // Start grouping executed commands into one undo step named `label`.
+    // Transactions nest, everything up to the outermost `commit` is one step.
+    pub fn begin(&mut self, label: &str) {
+        match self.transaction.as_mut() {
+            Some(transaction) => transaction.depth += 1,
+            None => {
+                self.transaction = Some(Transaction {
+                    label: label.to_string(),
+                    commands: vec![],
+                    depth: 0,
+                })
+            }
+        }
+    }
+
+    // Close the innermost transaction. An empty transaction records nothing.
+    // Panics if no transaction is open.
+    pub fn commit(&mut self) {
+        let transaction = self.transaction.as_mut().expect("no open transaction");
+        if transaction.depth > 0 {
+            transaction.depth -= 1;
+            return;
+        }
+
+        let transaction = self.transaction.take().unwrap();
+        if !transaction.commands.is_empty() {
+            self.record(Entry {
+                label: transaction.label,
+                commands: transaction.commands,
+            });
+        }
+    }
+
+    // Revert everything executed in the open transaction, outer levels
+    // included, and discard it. Panics if no transaction is open.
+    pub fn rollback(&mut self, target: &mut C::Target) {
+        let transaction = self.transaction.take().expect("no open transaction");
+        for mut command in transaction.commands.into_iter().rev() {
+            command.revert(target);
+        }
+    }
+
+    pub fn in_transaction(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    // Revert the latest step, false if there is nothing to undo. Panics if a
+    // transaction is open.
+    pub fn undo(&mut self, target: &mut C::Target) -> bool {
+        assert!(!self.in_transaction(), "undo inside a transaction");
+        if !self.can_undo() {
+            return false;
+        }
+
+        let mut entry = self.undo.pop().unwrap();
+        entry.revert(target);
+        self.redo.push(entry);
+        true
+    }
+
+    // Apply the latest undone step again, false if there is nothing to redo.
+    // Panics if a transaction is open.
+    pub fn redo(&mut self, target: &mut C::Target) -> bool {
+        assert!(!self.in_transaction(), "redo inside a transaction");
+        let Some(mut entry) = self.redo.pop() else {
+            return false;
+        };
+
+        entry.apply(target);
+        // it came off the undo stack, so there is room for it
+        self.undo.push(entry);
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo_len() > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo_len(&self) -> usize {
+        self.undo.size() - self.evicted
+    }
+
+    pub fn redo_len(&self) -> usize {
+        self.redo.size()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // what `undo` would revert
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo_labels().next()
+    }
+
+    // what `redo` would apply
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo_labels().next()
+    }
+
+    // latest first
+    pub fn undo_labels(&self) -> impl Iterator<Item = &str> {
+        self.undo
+            .iter()
+            .take(self.undo_len())
+            .map(|entry| entry.label.as_str())
+    }
+
+    // next to redo first
+    pub fn redo_labels(&self) -> impl Iterator<Item = &str> {
+        self.redo.iter().map(|entry| entry.label.as_str())
+    }
+
+    // forget every step, the target is left as it is
+    pub fn clear(&mut self) {
+        self.undo = LinkStack::new();
+        self.redo = LinkStack::new();
+        self.evicted = 0;
+        self.transaction = None;
+    }
+
+    // a new step, so whatever was undone can't be redone anymore
+    fn record(&mut self, entry: Entry<C>) {
+        self.redo = LinkStack::new();
+        self.undo.push(entry);
+        if self.undo_len() > self.capacity {
+            self.evicted += 1;
+        }
+        if self.evicted > 0 && self.evicted >= self.capacity {
+            self.drop_evicted();
+        }
+    }
+
+    fn drop_evicted(&mut self) {
+        let mut kept = LinkStack::new();
+        for _ in 0..self.undo_len() {
+            kept.push(self.undo.pop().unwrap());
+        }
+        // only evicted entries are left
+        self.undo = LinkStack::new();
+        while let Some(entry) = kept.pop() {
+            self.undo.push(entry);
+        }
+        self.evicted = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // text editing commands over a String
+    #[derive(Debug)]
+    enum Edit {
+        Insert {
+            at: usize,
+            text: String,
+        },
+        Delete {
+            at: usize,
+            len: usize,
+            deleted: String,
+        },
+    }
+
+    fn insert(at: usize, text: &str) -> Edit {
+        Edit::Insert {
+            at,
+            text: text.to_string(),
+        }
+    }
+
+    fn delete(at: usize, len: usize) -> Edit {
+        Edit::Delete {
+            at,
+            len,
+            deleted: String::new(),
+        }
+    }
+
+    impl Command for Edit {
+        type Target = String;
+
+        fn apply(&mut self, target: &mut String) {
+            match self {
+                Edit::Insert { at, text } => target.insert_str(*at, text),
+                Edit::Delete { at, len, deleted } => {
+                    *deleted = target.drain(*at..*at + *len).collect();
+                }
+            }
+        }
+
+        fn revert(&mut self, target: &mut String) {
+            match self {
+                Edit::Insert { at, text } => {
+                    target.drain(*at..*at + text.len());
+                }
+                Edit::Delete { at, deleted, .. } => target.insert_str(*at, deleted),
+            }
+        }
+
+        fn label(&self) -> &str {
+            match self {
+                Edit::Insert { .. } => "insert",
+                Edit::Delete { .. } => "delete",
+            }
+        }
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut text = String::new();
+        let mut history = History::new(10);
+        assert!(!history.undo(&mut text));
+        assert!(!history.redo(&mut text));
+
+        history.execute(insert(0, "hello"), &mut text);
+        history.execute(insert(5, " world"), &mut text);
+        history.execute(delete(0, 1), &mut text);
+        assert_eq!(text, "ello world");
+        assert_eq!(
+            history.undo_labels().collect::<Vec<_>>(),
+            ["delete", "insert", "insert"]
+        );
+
+        assert!(history.undo(&mut text));
+        assert_eq!(text, "hello world");
+        assert!(history.undo(&mut text));
+        assert_eq!(text, "hello");
+        assert_eq!(history.undo_label(), Some("insert"));
+        assert_eq!(
+            history.redo_labels().collect::<Vec<_>>(),
+            ["insert", "delete"]
+        );
+
+        assert!(history.redo(&mut text));
+        assert_eq!(text, "hello world");
+        assert!(history.redo(&mut text));
+        assert_eq!(text, "ello world");
+        assert!(!history.redo(&mut text));
+
+        while history.undo(&mut text) {}
+        assert_eq!(text, "");
+        assert_eq!((history.undo_len(), history.redo_len()), (0, 3));
+    }
+
+    #[test]
+    fn new_command_drops_redo() {
+        let mut text = String::new();
+        let mut history = History::new(10);
+        history.execute(insert(0, "ab"), &mut text);
+        history.execute(insert(2, "cd"), &mut text);
+        history.undo(&mut text);
+        assert!(history.can_redo());
+
+        history.execute(insert(2, "xy"), &mut text);
+        assert_eq!(text, "abxy");
+        assert!(!history.can_redo());
+        assert_eq!(history.redo_label(), None);
+        assert!(!history.redo(&mut text));
+    }
+
+    #[test]
+    fn capacity() {
+        let mut text = String::new();
+        let mut history = History::new(3);
+        for (i, ch) in "abcdefgh".chars().enumerate() {
+            history.execute(insert(i, &ch.to_string()), &mut text);
+            assert_eq!(history.undo_len(), (i + 1).min(3));
+        }
+
+        // only the three latest can be undone
+        while history.undo(&mut text) {}
+        assert_eq!(text, "abcde");
+        assert_eq!(history.redo_len(), 3);
+
+        // redoing and executing again still evicts correctly
+        history.redo(&mut text);
+        history.redo(&mut text);
+        history.execute(insert(7, "!"), &mut text);
+        history.execute(insert(8, "?"), &mut text);
+        assert_eq!(text, "abcdefg!?");
+        assert_eq!(history.undo_len(), 3);
+        while history.undo(&mut text) {}
+        assert_eq!(text, "abcdef");
+
+        // nothing is ever kept without capacity
+        let mut history = History::new(0);
+        history.execute(insert(0, "z"), &mut text);
+        assert!(!history.can_undo());
+        assert_eq!(history.undo_label(), None);
+    }
+
+    #[test]
+    fn transactions() {
+        let mut text = String::from("hello world");
+        let mut history = History::new(10);
+
+        // replace "world" as one step
+        history.begin("replace");
+        history.execute(delete(6, 5), &mut text);
+        history.execute(insert(6, "there"), &mut text);
+        assert!(history.in_transaction());
+        assert!(!history.can_undo());
+        history.commit();
+
+        assert_eq!(text, "hello there");
+        assert_eq!(history.undo_label(), Some("replace"));
+        assert_eq!(history.undo_len(), 1);
+
+        history.undo(&mut text);
+        assert_eq!(text, "hello world");
+        history.redo(&mut text);
+        assert_eq!(text, "hello there");
+
+        // nested ones join the outermost
+        history.begin("outer");
+        history.execute(insert(0, "<"), &mut text);
+        history.begin("inner");
+        history.execute(insert(12, ">"), &mut text);
+        history.commit();
+        assert!(history.in_transaction());
+        history.commit();
+        assert_eq!(text, "<hello there>");
+        assert_eq!(
+            history.undo_labels().collect::<Vec<_>>(),
+            ["outer", "replace"]
+        );
+
+        // empty ones leave no step
+        history.begin("nothing");
+        history.commit();
+        assert_eq!(history.undo_len(), 2);
+
+        // rolled back ones leave neither a step nor a change
+        history.begin("discard");
+        history.execute(delete(0, 1), &mut text);
+        history.execute(insert(0, "["), &mut text);
+        history.rollback(&mut text);
+        assert_eq!(text, "<hello there>");
+        assert!(!history.in_transaction());
+        assert_eq!(history.undo_label(), Some("outer"));
+
+        while history.undo(&mut text) {}
+        assert_eq!(text, "hello world");
+    }
+
+    #[test]
+    fn rollback_keeps_redo() {
+        let mut text = String::new();
+        let mut history = History::new(10);
+        history.execute(insert(0, "ab"), &mut text);
+        history.execute(insert(2, "cd"), &mut text);
+        history.undo(&mut text);
+
+        // a rolled back transaction records nothing, so redo survives it
+        history.begin("discard");
+        history.execute(insert(0, "x"), &mut text);
+        history.rollback(&mut text);
+        assert_eq!(text, "ab");
+        assert_eq!(history.redo_label(), Some("insert"));
+        assert!(history.redo(&mut text));
+        assert_eq!(text, "abcd");
+
+        // and so does an empty one
+        history.undo(&mut text);
+        history.begin("nothing");
+        history.commit();
+        assert!(history.can_redo());
+
+        // a committed one is a new step and drops it
+        history.begin("append");
+        history.execute(insert(2, "!"), &mut text);
+        assert!(history.can_redo());
+        history.commit();
+        assert!(!history.can_redo());
+        assert_eq!(text, "ab!");
+    }
+
+    #[test]
+    #[should_panic]
+    fn undo_in_transaction() {
+        let mut text = String::new();
+        let mut history: History<Edit> = History::new(10);
+        history.begin("open");
+        history.undo(&mut text);
+    }
+
+    #[test]
+    fn many() {
+        // evicted entries are dropped in batches as the history runs on
+        let mut count = 0;
+        let mut history = History::new(100);
+
+        struct Add;
+        impl Command for Add {
+            type Target = i32;
+            fn apply(&mut self, target: &mut i32) {
+                *target += 1;
+            }
+            fn revert(&mut self, target: &mut i32) {
+                *target -= 1;
+            }
+            fn label(&self) -> &str {
+                "add"
+            }
+        }
+
+        for _ in 0..100_000 {
+            history.execute(Add, &mut count);
+            assert!(history.undo.size() <= 200);
+        }
+        assert_eq!(history.undo_len(), 100);
+        while history.undo(&mut count) {}
+        assert_eq!(count, 100_000 - 100);
+    }
+}
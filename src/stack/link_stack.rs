@@ -1,3 +1,5 @@
+use super::Stack;
+
 pub struct LinkStack<T> {
     top: Option<Link<T>>,
     size: usize,
@@ -16,7 +18,13 @@ impl<T> Node<T> {
     }
 }
 
-impl<T: Clone> LinkStack<T> {
+impl<T> Default for LinkStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LinkStack<T> {
     pub fn new() -> Self {
         LinkStack { top: None, size: 0 }
     }
@@ -50,17 +58,141 @@ impl<T: Clone> LinkStack<T> {
         self.size
     }
 
-    pub fn to_vec(&self) -> Vec<T> {
-        self._to_vec(self.top.as_ref())
+    // elements from the top down
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
     }
 
-    fn _to_vec(&self, head: Option<&Link<T>>) -> Vec<T> {
-        if let Some(head) = head {
-            let mut rest = self._to_vec(head.next.as_ref());
-            rest.insert(0, head.val.clone());
-            return rest;
+    // walks from the top down
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.top.as_deref(),
         }
-        vec![]
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.top.as_deref_mut(),
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.val
+        })
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref_mut();
+            &mut node.val
+        })
+    }
+}
+
+// pops every element, top first
+pub struct IntoIter<T>(LinkStack<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.size, Some(self.0.size))
+    }
+}
+
+impl<T> IntoIterator for LinkStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkStack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+// pushes in order, so the last element ends up on top
+impl<T> FromIterator<T> for LinkStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = LinkStack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl<T> Extend<T> for LinkStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl<T> Stack<T> for LinkStack<T> {
+    // never full
+    fn push(&mut self, val: T) -> Result<(), T> {
+        LinkStack::push(self, val);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        LinkStack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        LinkStack::peek(self)
+    }
+
+    fn size(&self) -> usize {
+        LinkStack::size(self)
+    }
+
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        LinkStack::to_vec(self)
     }
 }
 
@@ -102,4 +234,42 @@ mod tests {
         assert_eq!(stack.size(), 0);
         assert!(stack.is_empty());
     }
+
+    #[test]
+    fn iter() {
+        let mut stack: LinkStack<i32> = (1..=4).collect();
+
+        assert_eq!(stack.iter().collect::<Vec<_>>(), [&4, &3, &2, &1]);
+        assert_eq!(stack.size(), 4);
+
+        for val in stack.iter_mut() {
+            *val *= 10;
+        }
+        for val in &mut stack {
+            *val += 1;
+        }
+        assert_eq!((&stack).into_iter().sum::<i32>(), 104);
+        assert_eq!(stack.to_vec(), [41, 31, 21, 11]);
+
+        stack.extend([5, 6]);
+        assert_eq!(stack.peek(), Some(&6));
+        assert_eq!(
+            stack.into_iter().collect::<Vec<_>>(),
+            [6, 5, 41, 31, 21, 11]
+        );
+
+        let empty = LinkStack::<i32>::new();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.into_iter().next(), None);
+    }
+
+    #[test]
+    fn long() {
+        // deep enough to overflow a recursive walk
+        let stack: LinkStack<usize> = (0..1_000_000).collect();
+        let vec = stack.to_vec();
+        assert_eq!(vec.len(), 1_000_000);
+        assert_eq!(vec[0], 999_999);
+        assert_eq!(stack.into_iter().size_hint(), (1_000_000, Some(1_000_000)));
+    }
 }
@@ -0,0 +1,223 @@
+use std::{cmp::Ordering, marker::PhantomData};
+
+use super::{array_stack::ArrayStack, Stack};
+
+// Which extremum a `TrackedStack` answers.
+pub trait Extremum {
+    // `val` is the new extremum if it compares to the current one like this
+    // or equal
+    const KEEP: Ordering;
+}
+
+pub struct Min;
+
+impl Extremum for Min {
+    const KEEP: Ordering = Ordering::Less;
+}
+
+pub struct Max;
+
+impl Extremum for Max {
+    const KEEP: Ordering = Ordering::Greater;
+}
+
+// A stack that also answers its minimum in O(1).
+pub type MinStack<T> = TrackedStack<T, Min>;
+
+// A stack that also answers its maximum in O(1).
+pub type MaxStack<T> = TrackedStack<T, Max>;
+
+// Elements that were the extremum when pushed live on `extrema` instead of
+// `stack`, which only marks their place with `None`. The extremum is always
+// on top of `extrema` and every element is stored once.
+pub struct TrackedStack<T, E> {
+    stack: ArrayStack<Option<T>>,
+    extrema: ArrayStack<T>,
+    extremum: PhantomData<E>,
+}
+
+impl<T: Ord, E: Extremum> TrackedStack<T, E> {
+    pub fn new() -> Self {
+        TrackedStack {
+            stack: ArrayStack::growable(16),
+            extrema: ArrayStack::growable(16),
+            extremum: PhantomData,
+        }
+    }
+
+    pub fn push(&mut self, val: T) {
+        let keep = match self.extrema.peek() {
+            Some(extremum) => val.cmp(extremum) != E::KEEP.reverse(),
+            None => true,
+        };
+        // both growable, so never full
+        if keep {
+            let _ = self.extrema.push(val);
+            let _ = self.stack.push(None);
+        } else {
+            let _ = self.stack.push(Some(val));
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        match self.stack.pop()? {
+            Some(val) => Some(val),
+            None => self.extrema.pop(),
+        }
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        match self.stack.peek()? {
+            Some(val) => Some(val),
+            None => self.extrema.peek(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    pub fn size(&self) -> usize {
+        self.stack.size()
+    }
+
+    // elements from the top down
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let mut extrema = self.extrema.to_vec().into_iter();
+        self.stack
+            .to_vec()
+            .into_iter()
+            .map(|val| val.or_else(|| extrema.next()).unwrap())
+            .collect()
+    }
+}
+
+impl<T: Ord> TrackedStack<T, Min> {
+    pub fn min(&self) -> Option<&T> {
+        self.extrema.peek()
+    }
+}
+
+impl<T: Ord> TrackedStack<T, Max> {
+    pub fn max(&self) -> Option<&T> {
+        self.extrema.peek()
+    }
+}
+
+impl<T: Ord, E: Extremum> Default for TrackedStack<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, E: Extremum> Stack<T> for TrackedStack<T, E> {
+    fn push(&mut self, val: T) -> Result<(), T> {
+        TrackedStack::push(self, val);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        TrackedStack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        TrackedStack::peek(self)
+    }
+
+    fn size(&self) -> usize {
+        TrackedStack::size(self)
+    }
+
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        TrackedStack::to_vec(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min() {
+        let mut stack = MinStack::new();
+        assert_eq!(stack.min(), None);
+
+        let nums = [6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        let mut mins = vec![];
+        for num in nums {
+            stack.push(num);
+            mins.push(*stack.min().unwrap());
+        }
+        assert_eq!(mins, [6, 4, 2, 2, 2, 2, 1, 1, 1, 1, 1, 1]);
+
+        // the minimum comes back as elements leave
+        for i in (0..nums.len()).rev() {
+            assert_eq!(stack.min(), Some(&mins[i]));
+            assert_eq!(stack.pop(), Some(nums[i]));
+        }
+        assert_eq!(stack.min(), None);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn max() {
+        let mut stack = MaxStack::new();
+        for num in [3, 1, 5, 5, 2] {
+            stack.push(num);
+        }
+        assert_eq!(stack.max(), Some(&5));
+        assert_eq!(stack.to_vec(), [2, 5, 5, 1, 3]);
+
+        stack.pop();
+        stack.pop();
+        // the other 5 is still there
+        assert_eq!(stack.max(), Some(&5));
+        stack.pop();
+        assert_eq!(stack.max(), Some(&3));
+        assert_eq!(stack.peek(), Some(&1));
+    }
+
+    #[test]
+    fn against_scan() {
+        // compare with a linear scan under interleaved pushes and pops
+        let mut min = MinStack::new();
+        let mut max = MaxStack::new();
+        let mut model = vec![];
+        for i in 0..2000 {
+            if i % 3 == 2 {
+                assert_eq!(min.pop(), model.pop());
+                max.pop();
+            } else {
+                let num = (i * 7919) % 1009;
+                min.push(num);
+                max.push(num);
+                model.push(num);
+            }
+            assert_eq!(min.min(), model.iter().min());
+            assert_eq!(max.max(), model.iter().max());
+        }
+    }
+
+    #[test]
+    fn no_clone() {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Token(String);
+
+        let mut stack = MinStack::new();
+        for word in ["b", "c", "a", "a"] {
+            stack.push(Token(word.to_string()));
+        }
+        assert_eq!(stack.min(), Some(&Token("a".to_string())));
+        assert_eq!(stack.pop(), Some(Token("a".to_string())));
+        assert_eq!(stack.min(), Some(&Token("a".to_string())));
+        assert_eq!(stack.pop(), Some(Token("a".to_string())));
+        assert_eq!(stack.peek(), Some(&Token("c".to_string())));
+        assert_eq!(stack.min(), Some(&Token("b".to_string())));
+    }
+}
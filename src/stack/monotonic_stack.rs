@@ -0,0 +1,187 @@
+// Nearest greater or smaller neighbours for every element of a slice in one
+// linear pass. The pass keeps a stack of indices whose values stay monotonic,
+// each index is pushed and popped at most once.
+
+use super::array_stack::ArrayStack;
+
+pub struct MonotonicStack<'a, T> {
+    nums: &'a [T],
+}
+
+impl<'a, T: Ord> MonotonicStack<'a, T> {
+    pub fn new(nums: &'a [T]) -> Self {
+        MonotonicStack { nums }
+    }
+
+    // for every index, the first index after it holding a strictly greater
+    // value
+    pub fn next_greater(&self) -> Vec<Option<usize>> {
+        self.next_by(|waiting, num| num > waiting)
+    }
+
+    // for every index, the first index after it holding a strictly smaller
+    // value
+    pub fn next_smaller(&self) -> Vec<Option<usize>> {
+        self.next_by(|waiting, num| num < waiting)
+    }
+
+    // for every index, the last index before it holding a strictly greater
+    // value
+    pub fn previous_greater(&self) -> Vec<Option<usize>> {
+        self.previous_by(|before, num| before > num)
+    }
+
+    // for every index, the last index before it holding a strictly smaller
+    // value
+    pub fn previous_smaller(&self) -> Vec<Option<usize>> {
+        self.previous_by(|before, num| before < num)
+    }
+
+    // The stack holds indices still waiting for their answer, `found(waiting,
+    // num)` says whether `num` is it.
+    fn next_by<F>(&self, mut found: F) -> Vec<Option<usize>>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut result = vec![None; self.nums.len()];
+        let mut waiting: ArrayStack<usize> = ArrayStack::growable(16);
+
+        for (i, num) in self.nums.iter().enumerate() {
+            while let Some(&top) = waiting.peek() {
+                if !found(&self.nums[top], num) {
+                    break;
+                }
+                result[top] = Some(i);
+                waiting.pop();
+            }
+            waiting.push(i).unwrap();
+        }
+
+        result
+    }
+
+    // The stack holds the candidates left of the current index, `keep(before,
+    // num)` says whether `before` answers for `num`. A candidate that doesn't
+    // is shadowed by `num` for everything further right.
+    fn previous_by<F>(&self, mut keep: F) -> Vec<Option<usize>>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut result = Vec::with_capacity(self.nums.len());
+        let mut candidates: ArrayStack<usize> = ArrayStack::growable(16);
+
+        for (i, num) in self.nums.iter().enumerate() {
+            while let Some(&top) = candidates.peek() {
+                if keep(&self.nums[top], num) {
+                    break;
+                }
+                candidates.pop();
+            }
+            result.push(candidates.peek().copied());
+            candidates.push(i).unwrap();
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // quadratic reference
+    fn brute<T: Ord>(
+        nums: &[T],
+        forward: bool,
+        wanted: impl Fn(&T, &T) -> bool,
+    ) -> Vec<Option<usize>> {
+        (0..nums.len())
+            .map(|i| {
+                if forward {
+                    (i + 1..nums.len()).find(|&j| wanted(&nums[j], &nums[i]))
+                } else {
+                    (0..i).rev().find(|&j| wanted(&nums[j], &nums[i]))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn init() {
+        let nums = vec![6, 4, 2, 9, 4, 8, 1, 3, 7, 2, 3, 5];
+        let stack = MonotonicStack::new(&nums);
+
+        assert_eq!(
+            stack.next_greater(),
+            [
+                Some(3),
+                Some(3),
+                Some(3),
+                None,
+                Some(5),
+                None,
+                Some(7),
+                Some(8),
+                None,
+                Some(10),
+                Some(11),
+                None
+            ]
+        );
+        assert_eq!(
+            stack.previous_smaller(),
+            [
+                None,
+                None,
+                None,
+                Some(2),
+                Some(2),
+                Some(4),
+                None,
+                Some(6),
+                Some(7),
+                Some(6),
+                Some(9),
+                Some(10)
+            ]
+        );
+    }
+
+    #[test]
+    fn against_brute() {
+        let inputs: Vec<Vec<i32>> = vec![
+            vec![],
+            vec![1],
+            vec![7; 10],
+            (0..50).collect(),
+            (0..50).rev().collect(),
+            (0..500).map(|i| (i * 7919) % 1009 / 7).collect(),
+        ];
+
+        for nums in inputs {
+            let stack = MonotonicStack::new(&nums);
+            assert_eq!(stack.next_greater(), brute(&nums, true, |a, b| a > b));
+            assert_eq!(stack.next_smaller(), brute(&nums, true, |a, b| a < b));
+            assert_eq!(stack.previous_greater(), brute(&nums, false, |a, b| a > b));
+            assert_eq!(stack.previous_smaller(), brute(&nums, false, |a, b| a < b));
+        }
+    }
+
+    #[test]
+    fn largest_rectangle() {
+        // the classic use: each bar spans from its previous to its next
+        // smaller bar
+        let heights = [2, 1, 5, 6, 2, 3];
+        let stack = MonotonicStack::new(&heights);
+        let (left, right) = (stack.previous_smaller(), stack.next_smaller());
+
+        let largest = (0..heights.len())
+            .map(|i| {
+                let start = left[i].map_or(0, |j| j + 1);
+                let end = right[i].unwrap_or(heights.len());
+                heights[i] * (end - start)
+            })
+            .max();
+        assert_eq!(largest, Some(10));
+    }
+}
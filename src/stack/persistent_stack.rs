@@ -0,0 +1,281 @@
+use std::{fmt, rc::Rc};
+
+// An immutable stack, `push` and `pop` leave the stack alone and return a new
+// version sharing every node below the top with it. Cloning is O(1), which
+// makes snapshots for undo or backtracking free.
+pub struct PersistentStack<T> {
+    top: Option<Link<T>>,
+    size: usize,
+}
+
+type Link<T> = Rc<Node<T>>;
+
+struct Node<T> {
+    val: T,
+    next: Option<Link<T>>,
+}
+
+impl<T> Default for PersistentStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PersistentStack<T> {
+    pub fn new() -> Self {
+        PersistentStack { top: None, size: 0 }
+    }
+
+    // a new version with `val` on top of this one
+    pub fn push(&self, val: T) -> Self {
+        let top = Rc::new(Node {
+            val,
+            next: self.top.clone(),
+        });
+        PersistentStack {
+            top: Some(top),
+            size: self.size + 1,
+        }
+    }
+
+    // the top element and the version below it
+    pub fn pop(&self) -> Option<(&T, Self)> {
+        self.top.as_ref().map(|top| {
+            let rest = PersistentStack {
+                top: top.next.clone(),
+                size: self.size - 1,
+            };
+            (&top.val, rest)
+        })
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.top.as_ref().map(|top| &top.val)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // elements from the top down
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    // walks from the top down
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.top.as_deref(),
+            size: self.size,
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    size: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            self.size -= 1;
+            &node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// pushes in order, so the last element ends up on top
+impl<T> FromIterator<T> for PersistentStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(PersistentStack::new(), |stack, val| stack.push(val))
+    }
+}
+
+// shares every node, no matter T
+impl<T> Clone for PersistentStack<T> {
+    fn clone(&self) -> Self {
+        PersistentStack {
+            top: self.top.clone(),
+            size: self.size,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.size != other.size {
+            return false;
+        }
+        // versions sharing a top share everything below it too
+        let shared = match (&self.top, &other.top) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            _ => true,
+        };
+        shared || self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for PersistentStack<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for PersistentStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Unlink nodes no other version holds one at a time, the default drop would
+// recurse once per node.
+impl<T> Drop for PersistentStack<T> {
+    fn drop(&mut self) {
+        let mut link = self.top.take();
+        while let Some(node) = link {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => link = node.next.take(),
+                // still part of another version
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_pop() {
+        let empty = PersistentStack::new();
+        let one = empty.push(1);
+        let two = one.push(2);
+        let three = two.push(3);
+
+        assert_eq!(three.to_vec(), [3, 2, 1]);
+        assert_eq!(three.size(), 3);
+        assert_eq!(three.peek(), Some(&3));
+
+        let (top, rest) = three.pop().unwrap();
+        assert_eq!(*top, 3);
+        assert_eq!(rest, two);
+
+        let (top, rest) = rest.pop().unwrap();
+        assert_eq!(*top, 2);
+        let (top, rest) = rest.pop().unwrap();
+        assert_eq!(*top, 1);
+        assert!(rest.is_empty());
+        assert!(rest.pop().is_none());
+        assert_eq!(rest.peek(), None);
+
+        // nothing before changed
+        assert!(empty.is_empty());
+        assert_eq!(one.to_vec(), [1]);
+        assert_eq!(two.to_vec(), [2, 1]);
+        assert_eq!(three.to_vec(), [3, 2, 1]);
+    }
+
+    #[test]
+    fn versions() {
+        // two branches growing from the same base
+        let base: PersistentStack<i32> = (1..=3).collect();
+        let left = base.push(10).push(11);
+        let (_, popped) = base.pop().unwrap();
+        let right = popped.push(20);
+
+        assert_eq!(base.to_vec(), [3, 2, 1]);
+        assert_eq!(left.to_vec(), [11, 10, 3, 2, 1]);
+        assert_eq!(right.to_vec(), [20, 2, 1]);
+
+        // the tails are the same nodes, not copies
+        let below_left = left.pop().unwrap().1.pop().unwrap().1;
+        assert!(Rc::ptr_eq(
+            below_left.top.as_ref().unwrap(),
+            base.top.as_ref().unwrap()
+        ));
+        let below_right = right.pop().unwrap().1;
+        assert!(Rc::ptr_eq(
+            below_right.top.as_ref().unwrap(),
+            popped.top.as_ref().unwrap()
+        ));
+
+        // dropping versions leaves the others whole
+        drop(base);
+        drop(popped);
+        assert_eq!(left.to_vec(), [11, 10, 3, 2, 1]);
+        assert_eq!(right.to_vec(), [20, 2, 1]);
+    }
+
+    #[test]
+    fn equality() {
+        let a: PersistentStack<i32> = (0..5).collect();
+        let b: PersistentStack<i32> = (0..5).collect();
+        assert_eq!(a, b);
+        assert_eq!(a, a.clone());
+        assert_ne!(a, a.push(5));
+        assert_ne!(a, a.pop().unwrap().1);
+        assert_ne!(a, (1..6).collect());
+        assert_eq!(PersistentStack::<i32>::new(), PersistentStack::new());
+        assert_eq!(format!("{:?}", a), "[4, 3, 2, 1, 0]");
+    }
+
+    #[test]
+    fn iter() {
+        let stack: PersistentStack<i32> = (0..4).collect();
+        let mut iter = stack.iter();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.collect::<Vec<_>>(), [&2, &1, &0]);
+
+        let sum: i32 = (&stack).into_iter().sum();
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn no_clone() {
+        #[derive(Debug, PartialEq)]
+        struct Token(String);
+
+        let stack = PersistentStack::new().push(Token("a".to_string()));
+        let stack = stack.push(Token("b".to_string()));
+        assert_eq!(stack.peek(), Some(&Token("b".to_string())));
+        assert_eq!(stack.clone().size(), 2);
+    }
+
+    #[test]
+    fn long() {
+        // neither drop nor equality may recurse per node
+        let a: PersistentStack<i32> = (0..1_000_000).collect();
+        let b: PersistentStack<i32> = (0..1_000_000).collect();
+        assert_eq!(a, b);
+
+        let c = a.push(-1);
+        drop(a);
+        assert_eq!(c.size(), 1_000_001);
+        drop(b);
+        drop(c);
+    }
+}
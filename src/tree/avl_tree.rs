@@ -28,6 +28,12 @@ impl<T: Clone + Debug + Ord> AVLTreeNode<T> {
     }
 }
 
+impl<T: Clone + Debug + Ord> Default for AVLTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Clone + Debug + Ord> AVLTree<T> {
     pub fn new() -> Self {
         Self { root: None }
@@ -203,8 +209,8 @@ impl<T: Clone + Debug + Ord> AVLTree<T> {
                             let mut to_change = to_remove.borrow().right.clone().unwrap();
                             loop {
                                 let left = to_change.borrow().left.clone();
-                                if left.is_some() {
-                                    to_change = left.unwrap();
+                                if let Some(left) = left {
+                                    to_change = left;
                                 } else {
                                     break;
                                 }
@@ -26,6 +26,12 @@ impl<T: Clone + Debug + Ord> BinarySearchTreeNode<T> {
     }
 }
 
+impl<T: Clone + Debug + Ord> Default for BinarySearchTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Clone + Debug + Ord> BinarySearchTree<T> {
     pub fn new() -> Self {
         Self { root: None }
@@ -78,7 +84,7 @@ impl<T: Clone + Debug + Ord> BinarySearchTree<T> {
                 Less => {
                     Self::_insert_recursion(&mut right, val);
                 }
-                Equal => return,
+                Equal => (),
                 Greater => {
                     Self::_insert_recursion(&mut left, val);
                 }
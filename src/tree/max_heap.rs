@@ -3,6 +3,13 @@ use std::{fmt::Debug, mem::swap};
 pub struct MaxHeap<T: Clone + Debug + Ord> {
     max_heap: Vec<T>,
 }
+
+impl<T: Clone + Debug + Ord> Default for MaxHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Clone + Debug + Ord> MaxHeap<T> {
     pub fn new() -> Self {
         Self { max_heap: vec![] }